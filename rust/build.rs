@@ -0,0 +1,146 @@
+//! Generates the year modules and the solutions registry from the `src/year_*/problem_*.rs` files,
+//! so that adding a day only takes creating its file
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+const SOURCE_DIR: &str = "src";
+const MANIFEST: &str = "Cargo.toml";
+
+fn number_after(name: &str, prefix: &str, suffix: &str) -> Option<i32> {
+    return name
+        .strip_prefix(prefix)?
+        .strip_suffix(suffix)?
+        .parse()
+        .ok();
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SOURCE_DIR);
+    println!("cargo:rerun-if-changed={}", MANIFEST);
+
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").expect("set by cargo"));
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("set by cargo"));
+
+    let mut years: BTreeMap<i32, BTreeMap<i32, PathBuf>> = BTreeMap::new();
+    let mut duplicates: Vec<String> = Vec::new();
+
+    let source_dir = manifest_dir.join(SOURCE_DIR);
+    for entry in fs::read_dir(&source_dir).expect("could not read the source directory") {
+        let year_dir = entry.expect("could not read a source entry").path();
+        let year = match year_dir.file_name().and_then(|name| name.to_str()) {
+            Some(name) if year_dir.is_dir() => number_after(name, "year_", ""),
+            _ => None,
+        };
+        let year = match year {
+            Some(year) => year,
+            None => continue,
+        };
+
+        let mut files: Vec<PathBuf> = fs::read_dir(&year_dir)
+            .expect("could not read a year directory")
+            .map(|entry| entry.expect("could not read a year entry").path())
+            .collect();
+        files.sort();
+
+        for file in files {
+            let day = match file.file_name().and_then(|name| name.to_str()) {
+                Some(name) => number_after(name, "problem_", ".rs"),
+                None => None,
+            };
+            let day = match day {
+                Some(day) => day,
+                None => continue,
+            };
+
+            let days = years.entry(year).or_default();
+            if let Some(registered) = days.get(&day) {
+                duplicates.push(format!(
+                    "day {} of {} is registered by both {} and {}",
+                    day,
+                    year,
+                    registered.display(),
+                    file.display()
+                ));
+                continue;
+            }
+            days.insert(day, file);
+        }
+    }
+
+    // every year is gated behind a `year-YYYY` cargo feature, which must be declared in Cargo.toml:
+    // an undeclared one is allowed as a cfg value, but its year is compiled out until it is declared
+    let manifest =
+        fs::read_to_string(manifest_dir.join(MANIFEST)).expect("could not read the manifest");
+    for year in years.keys() {
+        let feature = format!("year-{}", year);
+        println!("cargo::rustc-check-cfg=cfg(feature, values({:?}))", feature);
+        let declared = manifest.lines().any(|line| {
+            line.split_once('=')
+                .is_some_and(|(name, _)| name.trim() == feature)
+        });
+        if !declared {
+            println!(
+                "cargo::warning=src/year_{} is compiled out, declare a {} feature in {} and enable it by default",
+                year, feature, MANIFEST
+            );
+        }
+    }
+
+    let mut modules = String::new();
+    let mut registry = String::from(
+        "#[allow(unused_mut)]\nfn registered() -> Vec<Solution> {\n    let mut solutions: Vec<Solution> = Vec::new();\n",
+    );
+    let mut compiled_out = String::from(
+        "/// Years with solutions in the source tree whose cargo feature is disabled\n#[allow(unused_mut, clippy::vec_init_then_push)]\npub fn compiled_out_years() -> Vec<i32> {\n    let mut years: Vec<i32> = Vec::new();\n",
+    );
+    for duplicate in duplicates.iter() {
+        writeln!(modules, "compile_error!({:?});", duplicate).unwrap();
+    }
+    for (year, days) in years.iter() {
+        let feature = format!("#[cfg(feature = \"year-{}\")]", year);
+        writeln!(modules, "{}\npub mod year_{} {{", feature, year).unwrap();
+        writeln!(
+            compiled_out,
+            "    #[cfg(not(feature = \"year-{}\"))]\n    years.push({});",
+            year, year
+        )
+        .unwrap();
+        for (day, file) in days.iter() {
+            writeln!(
+                modules,
+                "    #[path = {:?}]\n    pub mod {};",
+                file.display().to_string(),
+                module_name(file)
+            )
+            .unwrap();
+            writeln!(
+                registry,
+                "    {}\n    solutions.extend(crate::year_{}::{}::solutions({}, {}));",
+                feature,
+                year,
+                module_name(file),
+                year,
+                day
+            )
+            .unwrap();
+        }
+        writeln!(modules, "}}").unwrap();
+    }
+    registry.push_str("    return solutions;\n}\n\n");
+    compiled_out.push_str("    return years;\n}\n");
+    registry.push_str(&compiled_out);
+
+    fs::write(out_dir.join("years.rs"), modules).expect("could not write the year modules");
+    fs::write(out_dir.join("registry.rs"), registry).expect("could not write the registry");
+}
+
+fn module_name(file: &Path) -> String {
+    return file
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .expect("problem files have utf-8 names")
+        .to_string();
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct AllocationStats {
+    /// Highest amount of heap memory held at once, on top of what was held before tracking began
+    pub peak_bytes: u64,
+    pub allocated_bytes: u64,
+    pub allocations: u64,
+}
+
+impl AllocationStats {
+    /// Combines the stats of two separate runs, keeping the highest of the two peaks
+    pub fn merge(self, other: AllocationStats) -> AllocationStats {
+        return AllocationStats {
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+            allocated_bytes: self.allocated_bytes + other.allocated_bytes,
+            allocations: self.allocations + other.allocations,
+        };
+    }
+}
+
+pub fn prettify_bytes(value: u64) -> String {
+    if value < 2560 {
+        format!("{}B", value)
+    } else if value < 2560 * 1024 {
+        format!("{}KiB", value / 1024)
+    } else if value < 2560 * 1024 * 1024 {
+        format!("{}MiB", value / 1024 / 1024)
+    } else {
+        format!("{}GiB", value / 1024 / 1024 / 1024)
+    }
+}
+
+#[cfg(feature = "alloc-profile")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use super::AllocationStats;
+
+    thread_local! {
+        static LIVE: Cell<u64> = const { Cell::new(0) };
+        static PEAK: Cell<u64> = const { Cell::new(0) };
+        static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+        static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    }
+
+    /// Counts the allocations of each thread, so that solutions running in parallel don't mix their stats
+    struct CountingAllocator;
+
+    fn on_allocation(size: usize) {
+        _ = LIVE.try_with(|live| {
+            let now_live = live.get() + size as u64;
+            live.set(now_live);
+            _ = PEAK.try_with(|peak| peak.set(peak.get().max(now_live)));
+        });
+        _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + size as u64));
+        _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+    }
+
+    fn on_deallocation(size: usize) {
+        _ = LIVE.try_with(|live| live.set(live.get().saturating_sub(size as u64)));
+    }
+
+    // SAFETY: every operation is delegated to the system allocator, only bookkeeping is added
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let pointer = unsafe { System.alloc(layout) };
+            if !pointer.is_null() {
+                on_allocation(layout.size());
+            }
+            return pointer;
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let pointer = unsafe { System.alloc_zeroed(layout) };
+            if !pointer.is_null() {
+                on_allocation(layout.size());
+            }
+            return pointer;
+        }
+
+        unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(pointer, layout) };
+            on_deallocation(layout.size());
+        }
+
+        unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_pointer = unsafe { System.realloc(pointer, layout, new_size) };
+            if !new_pointer.is_null() {
+                on_deallocation(layout.size());
+                on_allocation(new_size);
+            }
+            return new_pointer;
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    pub fn track<T>(f: impl FnOnce() -> T) -> (T, AllocationStats) {
+        let live_before = LIVE.get();
+        let allocated_before = ALLOCATED.get();
+        let allocations_before = ALLOCATIONS.get();
+        PEAK.set(live_before);
+
+        let result = f();
+
+        let stats = AllocationStats {
+            peak_bytes: PEAK.get() - live_before,
+            allocated_bytes: ALLOCATED.get() - allocated_before,
+            allocations: ALLOCATIONS.get() - allocations_before,
+        };
+        return (result, stats);
+    }
+}
+
+/// Runs `f`, reporting the allocations it made on the current thread when the `alloc-profile` feature is enabled
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Option<AllocationStats>) {
+    #[cfg(feature = "alloc-profile")]
+    {
+        let (result, stats) = counting::track(f);
+        return (result, Some(stats));
+    }
+
+    #[cfg(not(feature = "alloc-profile"))]
+    return (f(), None);
+}
@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Answer of a puzzle part, keeping its type so that it can be compared sensibly
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Answer {
+    Integer(i64),
+    Unsigned(u64),
+    Text(Cow<'static, str>),
+    /// Answer spanning several lines, such as letters drawn with ASCII art
+    Grid(Cow<'static, str>),
+}
+
+macro_rules! answer_from_integer {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    return Answer::$variant(value as $target);
+                }
+            }
+        )+
+    };
+}
+
+answer_from_integer!(Integer, i64, i8, i16, i32, i64, isize);
+answer_from_integer!(Unsigned, u64, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        return match value.trim().contains('\n') {
+            true => Answer::Grid(Cow::Owned(value)),
+            false => Answer::Text(Cow::Owned(value)),
+        };
+    }
+}
+
+impl From<&'static str> for Answer {
+    fn from(value: &'static str) -> Self {
+        return match value.trim().contains('\n') {
+            true => Answer::Grid(Cow::Borrowed(value)),
+            false => Answer::Text(Cow::Borrowed(value)),
+        };
+    }
+}
+
+/// Answer as written in a data file, either as a TOML integer or as a string
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum StoredAnswer {
+    Integer(i64),
+    Text(String),
+}
+
+impl From<StoredAnswer> for Answer {
+    fn from(value: StoredAnswer) -> Self {
+        return match value {
+            StoredAnswer::Integer(value) => Answer::Integer(value),
+            StoredAnswer::Text(text) => Answer::from(text),
+        };
+    }
+}
+
+impl From<&Answer> for StoredAnswer {
+    fn from(value: &Answer) -> Self {
+        return match value {
+            Answer::Integer(value) => StoredAnswer::Integer(*value),
+            Answer::Unsigned(value) => match i64::try_from(*value) {
+                Ok(value) => StoredAnswer::Integer(value),
+                Err(_) => StoredAnswer::Text(value.to_string()),
+            },
+            Answer::Text(text) | Answer::Grid(text) => StoredAnswer::Text(text.to_string()),
+        };
+    }
+}
+
+/// Values that can be written as the expected answer of a registered solution
+pub trait ExpectedValue {
+    fn into_answer(self) -> Answer;
+}
+
+impl ExpectedValue for i64 {
+    fn into_answer(self) -> Answer {
+        return Answer::Integer(self);
+    }
+}
+
+impl ExpectedValue for &'static str {
+    fn into_answer(self) -> Answer {
+        return Answer::from(self);
+    }
+}
+
+impl Answer {
+    pub fn expected(value: impl ExpectedValue) -> Answer {
+        return value.into_answer();
+    }
+
+    fn as_number(&self) -> Option<i128> {
+        return match self {
+            Answer::Integer(value) => Some(*value as i128),
+            Answer::Unsigned(value) => Some(*value as i128),
+            Answer::Text(text) => text.trim().parse::<i128>().ok(),
+            Answer::Grid(_) => None,
+        };
+    }
+
+    fn grid_lines(grid: &str) -> Vec<&str> {
+        return grid
+            .trim_matches('\n')
+            .lines()
+            .map(|line| line.trim_end())
+            .collect();
+    }
+
+    /// Whether this answer matches the expected one: numbers are compared by value, even when one of
+    /// them is written as text, text ignores surrounding whitespace and grids ignore trailing whitespace
+    pub fn matches(&self, expected: &Answer) -> bool {
+        return match (self, expected) {
+            (Answer::Grid(grid), Answer::Grid(expected)) => {
+                Answer::grid_lines(grid) == Answer::grid_lines(expected)
+            }
+            (Answer::Grid(_), _) | (_, Answer::Grid(_)) => false,
+            (Answer::Text(text), Answer::Text(expected)) => text.trim() == expected.trim(),
+            (answer, expected) => match (answer.as_number(), expected.as_number()) {
+                (Some(answer), Some(expected)) => answer == expected,
+                _ => false,
+            },
+        };
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        return matches!(self, Answer::Grid(_));
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(grid) => write!(f, "{}", grid.trim_matches('\n')),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_written_as_text_match_by_value() {
+        assert!(Answer::from("0440979").matches(&Answer::expected(440979)));
+        assert!(Answer::from(440979).matches(&Answer::from("0440979")));
+        assert!(Answer::from(" 440979\n").matches(&Answer::expected(440979)));
+        assert!(!Answer::from("440978").matches(&Answer::expected(440979)));
+    }
+
+    #[test]
+    fn unsigned_and_signed_integers_match_by_value() {
+        assert!(Answer::from(42usize).matches(&Answer::expected(42)));
+        assert!(Answer::from(42i32).matches(&Answer::from(42u64)));
+        assert!(!Answer::from(u64::MAX).matches(&Answer::expected(-1)));
+    }
+
+    #[test]
+    fn text_ignores_surrounding_whitespace() {
+        assert!(Answer::from(String::from("abc\n")).matches(&Answer::expected("abc")));
+        assert!(Answer::from("  abc").matches(&Answer::expected("abc ")));
+        assert!(!Answer::from("a bc").matches(&Answer::expected("abc")));
+        assert!(!Answer::from("ABC").matches(&Answer::expected("abc")));
+    }
+
+    #[test]
+    fn grids_ignore_trailing_whitespace() {
+        let answer = Answer::from(String::from("\n#..#  \n####\n#..# \n"));
+        assert!(answer.is_multiline());
+        assert!(answer.matches(&Answer::expected("#..#\n####\n#..#")));
+        assert!(!answer.matches(&Answer::expected(" #..#\n####\n#..#")));
+        assert!(!answer.matches(&Answer::expected("#..#\n####")));
+    }
+
+    #[test]
+    fn grids_only_match_grids() {
+        let grid = Answer::expected("#.\n.#");
+        assert!(!grid.matches(&Answer::expected("#.")));
+        assert!(!Answer::expected("#.").matches(&grid));
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::answer::{Answer, StoredAnswer};
+use crate::config::LoadedConfig;
+use crate::input::InputResolver;
+use crate::output::{Record, Status};
+use crate::problems::Solution;
+
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+/// Year or day number, which TOML only allows as a string key
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+struct Key(i32);
+
+impl Serialize for Key {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(&self.0.to_string());
+    }
+}
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        return raw.parse::<i32>().map(Key).map_err(|_| {
+            de::Error::custom(format!("expected a year or day number, found {}", raw))
+        });
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_1: Option<StoredAnswer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_2: Option<StoredAnswer>,
+}
+
+impl DayAnswers {
+    fn part(&mut self, part: i32) -> Option<&mut Option<StoredAnswer>> {
+        return match part {
+            1 => Some(&mut self.part_1),
+            2 => Some(&mut self.part_2),
+            _ => None,
+        };
+    }
+}
+
+type YearAnswers = BTreeMap<Key, BTreeMap<Key, DayAnswers>>;
+
+/// Contents of the answers file: answers for the default inputs, keyed by year and day, and
+/// answers for each input profile in `[profile.<name>]` tables
+#[derive(Serialize, Deserialize, Default)]
+struct AnswersFile {
+    #[serde(flatten)]
+    years: YearAnswers,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    profile: BTreeMap<String, YearAnswers>,
+}
+
+/// Location of the answers file: the one set in the configuration, or else the one found among the
+/// puzzle inputs, or else where it would be created in the first puzzle inputs directory
+pub fn answers_path(
+    config: Option<&LoadedConfig>,
+    input_resolver: &InputResolver,
+) -> Option<PathBuf> {
+    if let Some(loaded) = config {
+        if let Some(answers_file) = &loaded.config.answers_file {
+            return Some(loaded.resolve(answers_file));
+        }
+    }
+
+    return input_resolver
+        .find(Path::new(ANSWERS_FILE_NAME))
+        .ok()
+        .or(input_resolver.default_location(Path::new(ANSWERS_FILE_NAME)));
+}
+
+pub struct ExpectedAnswers {
+    path: Option<PathBuf>,
+    profile: Option<String>,
+    file: AnswersFile,
+}
+
+impl ExpectedAnswers {
+    pub fn load(path: Option<PathBuf>, profile: Option<String>) -> Result<ExpectedAnswers> {
+        let file = match &path {
+            Some(path) if path.is_file() => {
+                let raw = fs::read_to_string(path)
+                    .with_context(|| format!("could not read answers file {}", path.display()))?;
+                toml::from_str::<AnswersFile>(&raw)
+                    .with_context(|| format!("could not parse answers file {}", path.display()))?
+            }
+            _ => AnswersFile::default(),
+        };
+
+        return Ok(ExpectedAnswers {
+            path,
+            profile,
+            file,
+        });
+    }
+
+    fn section(&self) -> Option<&YearAnswers> {
+        return match &self.profile {
+            Some(profile) => self.file.profile.get(profile),
+            None => Some(&self.file.years),
+        };
+    }
+
+    /// Expected answer of a solution for the selected inputs. The answers compiled into the
+    /// registry belong to the default inputs, so they are only a fallback when no profile is selected
+    pub fn for_solution(&self, solution: &Solution) -> Option<Answer> {
+        let stored = self
+            .section()
+            .and_then(|years| years.get(&Key(solution.year)))
+            .and_then(|days| days.get(&Key(solution.day)))
+            .and_then(|answers| match solution.part {
+                1 => answers.part_1.clone(),
+                2 => answers.part_2.clone(),
+                _ => None,
+            });
+
+        return match (stored, &self.profile) {
+            (Some(stored), _) => Some(stored.into()),
+            (None, Some(_)) => None,
+            (None, None) => solution.expected.clone(),
+        };
+    }
+
+    /// Expected answer of a solution for the input it is run on. An input given on the command line
+    /// is not the one the answers were recorded for, so nothing is expected of it
+    pub fn for_input(&self, solution: &Solution, input_resolver: &InputResolver) -> Option<Answer> {
+        return match input_resolver.is_explicit() {
+            true => None,
+            false => self.for_solution(solution),
+        };
+    }
+
+    /// Stores the answer of a record that had nothing to be compared against, returning whether it did
+    pub fn record(&mut self, record: &Record) -> bool {
+        let answer = match (&record.answer, record.status, &record.example) {
+            (Some(answer), Status::Unknown, None) => answer,
+            _ => return false,
+        };
+
+        let years = match &self.profile {
+            Some(profile) => self.file.profile.entry(profile.clone()).or_default(),
+            None => &mut self.file.years,
+        };
+        let slot = years
+            .entry(Key(record.year))
+            .or_default()
+            .entry(Key(record.day))
+            .or_default()
+            .part(record.part);
+
+        return match slot {
+            Some(slot) if slot.is_none() => {
+                *slot = Some(answer.into());
+                true
+            }
+            _ => false,
+        };
+    }
+
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = self.path.clone().ok_or(anyhow!(
+            "no location for the answers file, set answers_file in the config"
+        ))?;
+
+        let serialized =
+            toml::to_string(&self.file).context("could not serialize the expected answers")?;
+        fs::write(&path, serialized)
+            .with_context(|| format!("could not write answers file {}", path.display()))?;
+
+        return Ok(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+    use crate::day::Solver;
+    use crate::metadata::Metadata;
+    use crate::problems::DEFAULT_VARIANT;
+    use crate::timing::Measurement;
+
+    static METADATA: Metadata = Metadata {
+        title: None,
+        tags: &[],
+        notes: None,
+    };
+
+    fn solution(year: i32, day: i32, part: i32) -> Solution {
+        return Solution {
+            year,
+            day,
+            part,
+            variant: DEFAULT_VARIANT,
+            solution: Solver::Raw(|_| Ok(Answer::from(0))),
+            expected: None,
+            params: &[],
+            metadata: &METADATA,
+        };
+    }
+
+    fn answered(solution: &Solution, answer: Answer) -> Record {
+        return Record::new(solution, None, Ok(answer), Measurement::default(), None);
+    }
+
+    fn expected(answers: &ExpectedAnswers, solution: &Solution) -> Option<String> {
+        return answers
+            .for_solution(solution)
+            .map(|answer| answer.to_string());
+    }
+
+    #[test]
+    fn recorded_answers_survive_a_round_trip() {
+        let dir = env::temp_dir().join(format!("aoc-answers-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(ANSWERS_FILE_NAME);
+        fs::write(
+            &path,
+            concat!(
+                "[2020.1]\n",
+                "part_1 = 440979\n",
+                "\n",
+                "[profile.alt.2020.1]\n",
+                "part_1 = \"abc\"\n",
+            ),
+        )
+        .unwrap();
+
+        let (day_1_part_1, day_1_part_2, day_2_part_1) = (
+            solution(2020, 1, 1),
+            solution(2020, 1, 2),
+            solution(2020, 2, 1),
+        );
+
+        let mut default = ExpectedAnswers::load(Some(path.clone()), None).unwrap();
+        assert!(!default.record(&answered(&day_1_part_1, Answer::from(1))));
+        assert!(default.record(&answered(&day_1_part_2, Answer::from(82498112))));
+        default.save().unwrap();
+
+        let mut alt = ExpectedAnswers::load(Some(path.clone()), Some(String::from("alt"))).unwrap();
+        assert!(alt.record(&answered(&day_2_part_1, Answer::from("#.\n.#"))));
+        alt.save().unwrap();
+
+        let default = ExpectedAnswers::load(Some(path.clone()), None).unwrap();
+        assert_eq!(expected(&default, &day_1_part_1).as_deref(), Some("440979"));
+        assert_eq!(
+            expected(&default, &day_1_part_2).as_deref(),
+            Some("82498112")
+        );
+        assert_eq!(expected(&default, &day_2_part_1), None);
+
+        let alt = ExpectedAnswers::load(Some(path.clone()), Some(String::from("alt"))).unwrap();
+        assert_eq!(expected(&alt, &day_1_part_1).as_deref(), Some("abc"));
+        assert_eq!(expected(&alt, &day_1_part_2), None);
+        assert!(alt
+            .for_solution(&day_2_part_1)
+            .is_some_and(|answer| answer.matches(&Answer::expected("#.\n.#"))));
+
+        let missing = ExpectedAnswers::load(Some(path), Some(String::from("missing"))).unwrap();
+        assert_eq!(expected(&missing, &day_1_part_1), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::output::Record;
+use crate::timing::prettify_duration;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BaselineEntry {
+    pub year: i32,
+    pub day: i32,
+    pub part: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    pub example: Option<String>,
+    pub wall_ns: u64,
+    pub cpu_ns: u64,
+}
+
+impl BaselineEntry {
+    /// Timings of a record, if it produced an answer worth comparing against
+    pub fn from_record(record: &Record) -> Option<BaselineEntry> {
+        let time = record.time?;
+        record.answer.as_ref()?;
+
+        return Some(BaselineEntry {
+            year: record.year,
+            day: record.day,
+            part: record.part,
+            variant: record.variant.clone(),
+            example: record.example.clone(),
+            wall_ns: time.wall.as_nanos().try_into().unwrap_or(u64::MAX),
+            cpu_ns: time.cpu.as_nanos().try_into().unwrap_or(u64::MAX),
+        });
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn key(&self) -> (i32, i32, i32, &Option<String>, &Option<String>) {
+        return (self.year, self.day, self.part, &self.variant, &self.example);
+    }
+
+    fn matches(&self, other: &BaselineEntry) -> bool {
+        return self.key() == other.key();
+    }
+
+    fn label(&self) -> String {
+        let mut label = format!("{}/{} P{}", self.year, self.day, self.part);
+        if let Some(variant) = &self.variant {
+            label.push_str(&format!(" ({})", variant));
+        }
+        if let Some(example) = &self.example {
+            label.push_str(&format!(" [{}]", example));
+        }
+        return label;
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct Baseline {
+    pub entries: Vec<BaselineEntry>,
+}
+
+pub fn baseline_path(data_dir: &Path, name: &str) -> Result<PathBuf> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        bail!("invalid baseline name: {}", name);
+    }
+
+    return Ok(data_dir.join("baselines").join(format!("{}.json", name)));
+}
+
+/// Timings of the latest run of every solution, kept up to date by every run
+fn last_timings_path(data_dir: &Path) -> PathBuf {
+    return data_dir.join("last-timings.json");
+}
+
+impl Baseline {
+    fn load_path(path: &Path) -> Result<Baseline> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("could not read baseline {}", path.display()))?;
+
+        return serde_json::from_str(&raw)
+            .with_context(|| format!("could not parse baseline {}", path.display()));
+    }
+
+    pub fn load(data_dir: &Path, name: &str) -> Result<Baseline> {
+        return Baseline::load_path(&baseline_path(data_dir, name)?);
+    }
+
+    /// Last known timings, empty when nothing was ever run
+    pub fn load_last(data_dir: &Path) -> Result<Baseline> {
+        let path = last_timings_path(data_dir);
+        return match path.is_file() {
+            true => Baseline::load_path(&path),
+            false => Ok(Baseline::default()),
+        };
+    }
+
+    /// Saves the baseline, keeping entries of a previous baseline with the same name that were not rerun
+    pub fn save(self, data_dir: &Path, name: &str) -> Result<PathBuf> {
+        return self.save_path(baseline_path(data_dir, name)?);
+    }
+
+    /// Updates the last known timings with those of this run
+    pub fn save_last(self, data_dir: &Path) -> Result<PathBuf> {
+        return self.save_path(last_timings_path(data_dir));
+    }
+
+    fn save_path(mut self, path: PathBuf) -> Result<PathBuf> {
+        if path.is_file() {
+            let previous = Baseline::load_path(&path)?;
+            for entry in previous.entries {
+                if !self.entries.iter().any(|current| current.matches(&entry)) {
+                    self.entries.push(entry);
+                }
+            }
+        }
+        self.entries.sort_by(|a, b| a.key().cmp(&b.key()));
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("could not create {}", parent.display()))?;
+        }
+        let serialized = serde_json::to_string_pretty(&self)?;
+        fs::write(&path, serialized)
+            .with_context(|| format!("could not write baseline {}", path.display()))?;
+
+        return Ok(path);
+    }
+}
+
+pub struct Comparison {
+    label: String,
+    before: Duration,
+    after: Duration,
+    /// Relative change of the wall time, where 0.1 means 10% slower
+    change: f64,
+    pub regressed: bool,
+}
+
+/// Compares the current timings against a baseline, flagging changes above `threshold` percent
+pub fn compare(baseline: &Baseline, current: &Baseline, threshold: f64) -> Vec<Comparison> {
+    return current
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let previous = baseline
+                .entries
+                .iter()
+                .find(|previous| previous.matches(entry))?;
+            let before = Duration::from_nanos(previous.wall_ns);
+            let after = Duration::from_nanos(entry.wall_ns);
+            let change = match previous.wall_ns {
+                0 => 0.0,
+                _ => (entry.wall_ns as f64 - previous.wall_ns as f64) / previous.wall_ns as f64,
+            };
+
+            Some(Comparison {
+                label: entry.label(),
+                before,
+                after,
+                change,
+                regressed: change * 100.0 > threshold,
+            })
+        })
+        .collect();
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let indicator = match (self.regressed, self.change < 0.0) {
+            (true, _) => "🐢",
+            (false, true) => "🚀",
+            (false, false) => "➖",
+        };
+
+        write!(
+            f,
+            "{} | {}: {} → {} ({:+.1}%)",
+            indicator,
+            self.label,
+            prettify_duration(self.before),
+            prettify_duration(self.after),
+            self.change * 100.0
+        )
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::allocations::AllocationStats;
+use crate::answer::Answer;
+use crate::panics::call_guarded;
+use crate::timing::{measure, prettify_duration, Measurement};
+
+pub struct BenchOptions {
+    pub warmup: u32,
+    pub runs: u32,
+    /// Time after which no more runs are started, warmup included, though one run is always measured
+    pub budget: Option<Duration>,
+}
+
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub max: Duration,
+}
+
+impl Statistics {
+    fn from_samples(samples: &[Duration]) -> Statistics {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let count = sorted.len();
+        if count == 0 {
+            return Statistics {
+                min: Duration::ZERO,
+                median: Duration::ZERO,
+                mean: Duration::ZERO,
+                stddev: Duration::ZERO,
+                max: Duration::ZERO,
+            };
+        }
+
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+
+        let mean_secs = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean_secs).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        return Statistics {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean_secs),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            max: sorted[count - 1],
+        };
+    }
+}
+
+impl std::fmt::Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {} · median {} · mean {} · σ {} · max {}",
+            prettify_duration(self.min),
+            prettify_duration(self.median),
+            prettify_duration(self.mean),
+            prettify_duration(self.stddev),
+            prettify_duration(self.max),
+        )
+    }
+}
+
+pub struct BenchReport {
+    pub runs: usize,
+    pub wall: Statistics,
+    pub cpu: Statistics,
+    pub allocations: Option<AllocationStats>,
+}
+
+impl BenchReport {
+    pub fn typical(&self) -> Measurement {
+        return Measurement {
+            wall: self.wall.median,
+            cpu: self.cpu.median,
+            allocations: self.allocations,
+        };
+    }
+}
+
+/// Runs a solution repeatedly, calling `prepare` outside of the timed section to build each run's input
+pub fn bench<I>(
+    prepare: impl Fn() -> I,
+    run: impl Fn(I) -> Result<Answer>,
+    opts: &BenchOptions,
+) -> (Result<Answer>, BenchReport) {
+    let started = Instant::now();
+    let budget_spent = || {
+        opts.budget
+            .is_some_and(|budget| started.elapsed() >= budget)
+    };
+
+    for _ in 0..opts.warmup {
+        if budget_spent() {
+            break;
+        }
+        _ = call_guarded(|| run(prepare()));
+    }
+
+    let mut result: Option<Result<Answer>> = None;
+    let mut wall_samples: Vec<Duration> = Vec::new();
+    let mut cpu_samples: Vec<Duration> = Vec::new();
+    let mut allocations: Option<AllocationStats> = None;
+    while wall_samples.len() < opts.runs.max(1) as usize {
+        let run_input = prepare();
+        let (run_result, measurement) = measure(|| call_guarded(|| run(run_input)));
+
+        wall_samples.push(measurement.wall);
+        cpu_samples.push(measurement.cpu);
+        allocations = measurement.allocations;
+
+        let failed = run_result.is_err();
+        result = Some(run_result);
+        if failed {
+            break;
+        }
+
+        if budget_spent() {
+            break;
+        }
+    }
+
+    let report = BenchReport {
+        runs: wall_samples.len(),
+        wall: Statistics::from_samples(&wall_samples),
+        cpu: Statistics::from_samples(&cpu_samples),
+        allocations,
+    };
+
+    return (
+        result.expect("at least one benchmark run is always executed"),
+        report,
+    );
+}
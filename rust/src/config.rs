@@ -0,0 +1,82 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+const CONFIG_ENV_VAR: &str = "AOC_CONFIG";
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub input_dir: Option<PathBuf>,
+    pub data_dir: Option<PathBuf>,
+    pub answers_file: Option<PathBuf>,
+}
+
+pub struct LoadedConfig {
+    pub path: PathBuf,
+    pub config: Config,
+}
+
+impl LoadedConfig {
+    /// Resolves a path found in the configuration relative to the configuration file itself
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        match self.path.parent() {
+            Some(parent) => parent.join(path),
+            None => path.to_path_buf(),
+        }
+    }
+}
+
+/// Directory where the runner keeps the state it produces, such as performance baselines
+pub fn data_dir(config: Option<&LoadedConfig>) -> Result<PathBuf> {
+    if let Some(loaded) = config {
+        if let Some(data_dir) = &loaded.config.data_dir {
+            return Ok(loaded.resolve(data_dir));
+        }
+    }
+
+    let data_home = match env::var_os("XDG_DATA_HOME") {
+        Some(data_home) => PathBuf::from(data_home),
+        None => PathBuf::from(
+            env::var_os("HOME").ok_or(anyhow!("could not determine the home directory"))?,
+        )
+        .join(".local")
+        .join("share"),
+    };
+
+    return Ok(data_home.join("aoc"));
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_home = match env::var_os("XDG_CONFIG_HOME") {
+        Some(config_home) => PathBuf::from(config_home),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    return Some(config_home.join("aoc").join("config.toml"));
+}
+
+pub fn load() -> Result<Option<LoadedConfig>> {
+    let path = match config_path() {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("could not read config file {}", path.display()))?;
+    let config: Config = toml::from_str(&raw)
+        .with_context(|| format!("could not parse config file {}", path.display()))?;
+
+    return Ok(Some(LoadedConfig { path, config }));
+}
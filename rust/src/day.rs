@@ -0,0 +1,71 @@
+use std::any::Any;
+
+use anyhow::{anyhow, Result};
+
+use crate::answer::Answer;
+
+/// A day's puzzle, split into parsing the input once and solving each part from the parsed value
+pub trait Day {
+    type Parsed: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+    fn part_1(parsed: &Self::Parsed) -> Result<Answer>;
+    fn part_2(parsed: &Self::Parsed) -> Result<Answer>;
+}
+
+/// Parsed input of a day, with its concrete type erased so it can be stored in the registry
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+pub enum Solver {
+    /// Solution working on the raw input, kept for days not yet migrated to `Day`
+    Raw(fn(String) -> Result<Answer>),
+    /// One part of a `Day`, whose parsed input can be shared with the other part
+    Parsed {
+        parse: fn(&str) -> Result<Parsed>,
+        solve: fn(&Parsed) -> Result<Answer>,
+    },
+}
+
+pub fn parse_erased<D: Day>(input: &str) -> Result<Parsed> {
+    return Ok(Box::new(D::parse(input)?));
+}
+
+/// Recovers the concrete parsed input of a day
+pub fn downcast<D: Day>(parsed: &Parsed) -> Result<&D::Parsed> {
+    return parsed
+        .downcast_ref::<D::Parsed>()
+        .ok_or(anyhow!("parsed input does not belong to this day"));
+}
+
+fn solve_erased<D: Day, const PART: i32>(parsed: &Parsed) -> Result<Answer> {
+    let parsed = downcast::<D>(parsed)?;
+
+    return match PART {
+        1 => D::part_1(parsed),
+        _ => D::part_2(parsed),
+    };
+}
+
+impl Solver {
+    /// Parses the input, if the solution does so separately, and solves it
+    pub fn solve(&self, input: &str) -> Result<Answer> {
+        return match self {
+            Solver::Raw(solve) => solve(input.to_string()),
+            Solver::Parsed { parse, solve } => solve(&parse(input)?),
+        };
+    }
+
+    pub const fn part_1<D: Day>() -> Solver {
+        return Solver::Parsed {
+            parse: parse_erased::<D>,
+            solve: solve_erased::<D, 1>,
+        };
+    }
+
+    pub const fn part_2<D: Day>() -> Solver {
+        return Solver::Parsed {
+            parse: parse_erased::<D>,
+            solve: solve_erased::<D, 2>,
+        };
+    }
+}
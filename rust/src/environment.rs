@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+
+use crate::answers::{self, ExpectedAnswers};
+use crate::config;
+use crate::input::{ExplicitInput, InputResolver};
+
+/// Configuration, inputs and stored answers the solutions are run against
+pub struct Environment {
+    pub input_resolver: InputResolver,
+    pub expected_answers: ExpectedAnswers,
+    /// Directory kept state is stored in, which cannot be determined without a home directory
+    data_dir: Result<PathBuf>,
+}
+
+impl Environment {
+    /// Loads the configuration and the expected answers, using the input given as `-` for stdin or
+    /// a file path, or else the inputs of the profile, when given, or the default ones
+    pub fn load(input: Option<&Path>, profile: Option<String>) -> Result<Environment> {
+        let config = config::load()?;
+        let explicit_input = input.map(ExplicitInput::from_argument).transpose()?;
+        let input_resolver = InputResolver::new(explicit_input, profile.clone(), config.as_ref());
+        let expected_answers = ExpectedAnswers::load(
+            answers::answers_path(config.as_ref(), &input_resolver),
+            profile,
+        )?;
+
+        return Ok(Environment {
+            input_resolver,
+            expected_answers,
+            data_dir: config::data_dir(config.as_ref()),
+        });
+    }
+
+    /// Data directory, when it could be determined
+    pub fn data_dir(&self) -> Option<&Path> {
+        return self.data_dir.as_deref().ok();
+    }
+
+    /// Data directory of a feature that cannot work without it
+    pub fn require_data_dir(&self) -> Result<&Path> {
+        return match &self.data_dir {
+            Ok(data_dir) => Ok(data_dir),
+            Err(e) => Err(anyhow!("{:#}", e)),
+        };
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::answer::{Answer, StoredAnswer};
+use crate::input::{InputError, InputResolver};
+use crate::params::Params;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExamplesFile {
+    #[serde(default, rename = "example")]
+    examples: Vec<ExampleEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExampleEntry {
+    name: String,
+    file: PathBuf,
+    part_1: Option<StoredAnswer>,
+    part_2: Option<StoredAnswer>,
+    #[serde(default)]
+    params: BTreeMap<String, toml::Value>,
+}
+
+impl ExampleEntry {
+    fn expected(&self, part: i32) -> Option<&StoredAnswer> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub expected: Answer,
+    pub params: Params,
+}
+
+/// Examples registered for a day, described by `{year}/{day}.examples.toml` next to the real inputs
+pub struct Examples {
+    path: PathBuf,
+    entries: Vec<ExampleEntry>,
+}
+
+impl Examples {
+    pub fn relative_path(year: i32, day: i32) -> PathBuf {
+        return Path::new(&year.to_string()).join(format!("{}.examples.toml", day));
+    }
+
+    pub fn load(resolver: &InputResolver, year: i32, day: i32) -> Result<Option<Examples>> {
+        let path = match resolver.find(&Examples::relative_path(year, day)) {
+            Ok(path) => path,
+            Err(InputError::NotFound(..)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("could not read examples file {}", path.display()))?;
+        let parsed: ExamplesFile = toml::from_str(&raw)
+            .with_context(|| format!("could not parse examples file {}", path.display()))?;
+
+        return Ok(Some(Examples {
+            path,
+            entries: parsed.examples,
+        }));
+    }
+
+    /// Manifest and example inputs of a day, or where its manifest would be created when there is none,
+    /// so that changes to any of them can be noticed
+    pub fn watched_files(resolver: &InputResolver, year: i32, day: i32) -> Vec<PathBuf> {
+        let relative = Examples::relative_path(year, day);
+        let examples = match Examples::load(resolver, year, day) {
+            Ok(Some(examples)) => examples,
+            Ok(None) | Err(_) => {
+                return Vec::from_iter(
+                    resolver
+                        .find(&relative)
+                        .ok()
+                        .or(resolver.default_location(&relative)),
+                );
+            }
+        };
+
+        let directory = examples.path.parent().unwrap_or(Path::new("."));
+        let mut files = vec![examples.path.clone()];
+        files.extend(
+            examples
+                .entries
+                .iter()
+                .map(|entry| directory.join(&entry.file)),
+        );
+        return files;
+    }
+
+    /// Reads every example that has an expected answer for the given part
+    pub fn for_part(&self, part: i32) -> Result<Vec<Example>> {
+        let directory = self.path.parent().unwrap_or(Path::new("."));
+
+        let mut examples: Vec<Example> = Vec::new();
+        for entry in self.entries.iter() {
+            let expected = match entry.expected(part) {
+                Some(expected) => expected,
+                None => continue,
+            };
+
+            let file = directory.join(&entry.file);
+            let input = fs::read_to_string(&file).with_context(|| {
+                format!("could not read example {}: {}", entry.name, file.display())
+            })?;
+
+            examples.push(Example {
+                name: entry.name.clone(),
+                input,
+                expected: expected.clone().into(),
+                params: entry
+                    .params
+                    .iter()
+                    .map(|(name, value)| match value {
+                        toml::Value::String(value) => (name.clone(), value.clone()),
+                        value => (name.clone(), value.to_string()),
+                    })
+                    .collect(),
+            });
+        }
+
+        return Ok(examples);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod char;
 pub mod lines;
 pub mod matrix;
+#[cfg(feature = "regex")]
 pub mod regex;
 pub mod traits;
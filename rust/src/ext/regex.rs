@@ -1,12 +1,16 @@
+pub use regex::Regex;
+
+/// Defines a function returning a regex compiled on first use, usable without depending on `regex`
 #[macro_export]
 macro_rules! static_regex {
     ($name:tt, $regex:expr) => {
-        fn $name() -> &'static regex::Regex {
+        fn $name() -> &'static $crate::ext::regex::Regex {
             use std::sync::OnceLock;
 
-            static REGEX: OnceLock<regex::Regex> = OnceLock::new();
-            return REGEX
-                .get_or_init(|| regex::Regex::new($regex).expect("regex could not be initialized"));
+            static REGEX: OnceLock<$crate::ext::regex::Regex> = OnceLock::new();
+            return REGEX.get_or_init(|| {
+                $crate::ext::regex::Regex::new($regex).expect("regex could not be initialized")
+            });
         }
     };
 }
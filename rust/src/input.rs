@@ -0,0 +1,192 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use thiserror::Error;
+
+use crate::config::LoadedConfig;
+
+const INPUT_DIR_ENV_VAR: &str = "AOC_INPUT_DIR";
+const INPUT_DIR_NAME: &str = "puzzle-inputs";
+
+#[derive(Error, Debug)]
+pub enum InputError {
+    #[error("could not read {0}: {1}")]
+    Read(String, io::Error),
+    #[error("could not find {0}, tried: {1}")]
+    NotFound(String, String),
+}
+
+pub enum ExplicitInput {
+    File(PathBuf),
+    Stdin(String),
+}
+
+impl ExplicitInput {
+    /// Interprets `-` as standard input, reading it eagerly, and anything else as a file path
+    pub fn from_argument(argument: &Path) -> Result<ExplicitInput> {
+        if argument == Path::new("-") {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| InputError::Read("standard input".into(), e))?;
+            return Ok(ExplicitInput::Stdin(input));
+        }
+
+        return Ok(ExplicitInput::File(argument.to_path_buf()));
+    }
+}
+
+struct Candidate {
+    directory: PathBuf,
+    origin: &'static str,
+}
+
+pub struct InputResolver {
+    explicit: Option<ExplicitInput>,
+    profile: Option<String>,
+    candidates: Vec<Candidate>,
+}
+
+fn find_upwards(start: &Path, name: &str) -> Option<PathBuf> {
+    return start
+        .ancestors()
+        .map(|ancestor| ancestor.join(name))
+        .find(|candidate| candidate.is_dir());
+}
+
+impl InputResolver {
+    /// Creates a resolver looking for inputs in the usual locations, within the profile's
+    /// subdirectory when an input profile is given
+    pub fn new(
+        explicit: Option<ExplicitInput>,
+        profile: Option<String>,
+        config: Option<&LoadedConfig>,
+    ) -> InputResolver {
+        let mut candidates: Vec<Candidate> = Vec::new();
+
+        if let Some(directory) = env::var_os(INPUT_DIR_ENV_VAR) {
+            candidates.push(Candidate {
+                directory: PathBuf::from(directory),
+                origin: INPUT_DIR_ENV_VAR,
+            });
+        }
+
+        if let Some(loaded) = config {
+            if let Some(directory) = &loaded.config.input_dir {
+                candidates.push(Candidate {
+                    directory: loaded.resolve(directory),
+                    origin: "config file",
+                });
+            }
+        }
+
+        if let Some(directory) = env::current_dir()
+            .ok()
+            .and_then(|cwd| find_upwards(&cwd, INPUT_DIR_NAME))
+        {
+            candidates.push(Candidate {
+                directory,
+                origin: "directory search",
+            });
+        }
+
+        return InputResolver {
+            explicit,
+            profile,
+            candidates,
+        };
+    }
+
+    /// Finds a file, given relative to the puzzle inputs directory, in the first location containing it
+    pub fn find(&self, relative: &Path) -> Result<PathBuf, InputError> {
+        for candidate in self.candidates.iter() {
+            let path = candidate.directory.join(relative);
+            if path.is_file() {
+                return Ok(path);
+            }
+        }
+
+        let tried = match self.candidates.is_empty() {
+            true => format!(
+                "no locations (set {} or create a {} directory)",
+                INPUT_DIR_ENV_VAR, INPUT_DIR_NAME
+            ),
+            false => self
+                .candidates
+                .iter()
+                .map(|candidate| {
+                    format!(
+                        "{} ({})",
+                        candidate.directory.join(relative).display(),
+                        candidate.origin
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+        };
+
+        return Err(InputError::NotFound(relative.display().to_string(), tried));
+    }
+
+    /// Where a file, given relative to the puzzle inputs directory, belongs when it does not exist yet
+    pub fn default_location(&self, relative: &Path) -> Option<PathBuf> {
+        return self
+            .candidates
+            .first()
+            .map(|candidate| candidate.directory.join(relative));
+    }
+
+    fn input_relative(&self, year: i32, day: i32) -> PathBuf {
+        let relative = Path::new(&year.to_string()).join(format!("{}.txt", day));
+        return match &self.profile {
+            Some(profile) => Path::new(profile).join(relative),
+            None => relative,
+        };
+    }
+
+    /// Whether the input was given on the command line rather than found in the inputs directory
+    pub fn is_explicit(&self) -> bool {
+        return self.explicit.is_some();
+    }
+
+    /// Whether the input is read from standard input, which cannot be watched
+    pub fn reads_stdin(&self) -> bool {
+        return matches!(self.explicit, Some(ExplicitInput::Stdin(_)));
+    }
+
+    /// Whether the inputs are the default ones, found in the inputs directory outside of any profile
+    pub fn is_default(&self) -> bool {
+        return self.explicit.is_none() && self.profile.is_none();
+    }
+
+    pub fn input_path(&self, year: i32, day: i32) -> Result<PathBuf, InputError> {
+        if let Some(ExplicitInput::File(path)) = &self.explicit {
+            return Ok(path.clone());
+        }
+
+        return self.find(&self.input_relative(year, day));
+    }
+
+    /// File the input of a day is read from, or where it would be created when it does not exist yet,
+    /// so that its creation can be noticed
+    pub fn watched_input(&self, year: i32, day: i32) -> Option<PathBuf> {
+        return match self.input_path(year, day) {
+            Ok(path) => Some(path),
+            Err(_) => self.default_location(&self.input_relative(year, day)),
+        };
+    }
+
+    pub fn read(&self, year: i32, day: i32) -> Result<String, InputError> {
+        if let Some(ExplicitInput::Stdin(input)) = &self.explicit {
+            return Ok(input.clone());
+        }
+
+        let path = self.input_path(year, day)?;
+        return fs::read_to_string(&path)
+            .map_err(|e| InputError::Read(path.display().to_string(), e));
+    }
+}
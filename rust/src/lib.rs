@@ -0,0 +1,67 @@
+//! Advent of Code solutions, with the registry of every compiled-in day and the utilities they share
+
+pub mod allocations;
+pub mod answer;
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod config;
+pub mod day;
+pub mod environment;
+pub mod examples;
+pub mod ext;
+pub mod input;
+pub mod metadata;
+pub mod output;
+pub mod panics;
+pub mod params;
+pub mod problems;
+pub mod report;
+pub mod runner;
+mod sandbox;
+pub mod selection;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod status;
+pub mod timing;
+pub mod variants;
+pub mod verify;
+pub mod watch;
+
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
+
+use anyhow::{anyhow, Result};
+
+use answer::Answer;
+
+/// Solves one part of a day's puzzle for the given input. A solution that panics, such as on an
+/// input it cannot handle, is reported as an error: the first call installs the panic hook used by
+/// the runner, which leaves panics raised outside of solutions to the previous hook
+pub fn solve(year: i32, day: i32, part: i32, input: &str) -> Result<Answer> {
+    let solution = problems::find(year, day, part).ok_or(anyhow!(
+        "no solution registered for {}/{} P{}",
+        year,
+        day,
+        part
+    ))?;
+
+    panics::install_hook();
+    return panics::call_guarded(|| solution.solution.solve(input));
+}
+
+#[cfg(all(test, feature = "year-2020"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panicking_solutions_are_reported_as_errors() {
+        let error = solve(2020, 12, 1, "é1").unwrap_err();
+        assert!(error.is::<panics::SolutionPanic>(), "got {}", error);
+    }
+
+    #[test]
+    fn unknown_solutions_are_reported_as_errors() {
+        let error = solve(2020, 26, 1, "").unwrap_err();
+        assert_eq!(error.to_string(), "no solution registered for 2020/26 P1");
+    }
+}
@@ -1,137 +1,496 @@
-pub mod ext;
-pub mod problems;
-pub mod year_2020;
+mod scaffold;
 
+use std::fs;
+#[cfg(feature = "serve")]
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::process;
+#[cfg(feature = "serve")]
+use std::thread;
 use std::time::{Duration, SystemTime};
-use std::{fs, path::Path, process};
 
-use clap::Parser;
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand};
 
-use problems::{Solution, SOLUTIONS};
+use aoc::baseline::Baseline;
+use aoc::bench::BenchOptions;
+use aoc::environment::Environment;
+use aoc::metadata::{self, Tag};
+use aoc::output::{OutputFormat, Record};
+use aoc::params::{self, Params};
+use aoc::problems::{Solution, SOLUTIONS};
+use aoc::report::{self, Report, ReportFormat};
+use aoc::runner::{self, ExecutionOptions, RunSettings, SandboxOptions};
+use aoc::selection::{self, Criteria, NumberSet, Selection, Skip};
+#[cfg(feature = "serve")]
+use aoc::serve::{self, ServeOptions};
+use aoc::status::Calendar;
+use aoc::verify::Verification;
+use aoc::watch;
+
+/// Highest memory limit, in MiB, whose size in bytes still fits in a u64
+const MAX_MEMORY_LIMIT_MIB: u64 = u64::MAX / (1024 * 1024);
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check the registry and the inputs, then run the selected solutions, failing on any problem
+    Verify,
+    /// Create the source file of a new day, given with --year and --day, from the template
+    New,
+    /// List the selected days with their titles, tags, variants, parameters and notes
+    List,
+    /// Draw the calendar of the selected years, with the progress and last known timing of every day
+    Status,
+    /// Run the selected solutions and write their answers and timings as a report
+    Report(ReportArguments),
+    /// Answer requests on localhost, solving the inputs posted to /solve/{year}/{day}/{part}
+    #[cfg(feature = "serve")]
+    Serve(ServeArguments),
+}
+
+#[derive(Args)]
+struct ReportArguments {
+    /// Format of the report
+    #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+    format: ReportFormat,
+    /// File to write the report to instead of standard output
+    #[arg(long, value_name = "PATH")]
+    file: Option<PathBuf>,
+    /// Replace the section between the report markers of this README with the report, which is
+    /// always written in Markdown there
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["file", "format"])]
+    readme: Option<PathBuf>,
+    /// Hide the answers, only telling whether they are correct
+    #[arg(long)]
+    mask_answers: bool,
+}
+
+#[cfg(feature = "serve")]
+#[derive(Args)]
+struct ServeArguments {
+    /// Port to listen on
+    #[arg(long, default_value_t = 8025)]
+    port: u16,
+    /// Seconds of wall time after which the solution answering a request is killed
+    #[arg(long, default_value = "10", value_parser = parse_seconds)]
+    request_timeout: Duration,
+    /// Number of inputs solved at the same time, by default the number of available cores
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    workers: Option<u16>,
+}
 
 #[derive(Parser)]
 #[command(about, long_about = None)]
 struct Arguments {
-    #[arg(short, long)]
-    year: Option<i32>,
-    #[arg(short, long)]
-    day: Option<i32>,
-    #[arg(short, long)]
-    part: Option<i32>,
-}
-
-fn filter_from_option<T: PartialEq + 'static>(value: Option<T>) -> impl Fn(T) -> bool {
-    move |target| match &value {
-        None => true,
-        Some(value) => target == *value,
-    }
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Years to select, as a list of numbers and ranges such as 2020,2022..=2024
+    #[arg(short, long, value_name = "YEARS", global = true)]
+    year: Option<NumberSet>,
+    /// Days to select, as a list of numbers and ranges such as 1..=5,9
+    #[arg(short, long, value_name = "DAYS", global = true)]
+    day: Option<NumberSet>,
+    /// Parts to select, as a list of numbers and ranges
+    #[arg(short, long, value_name = "PARTS", global = true)]
+    part: Option<NumberSet>,
+    /// Only select the highest registered day among the ones matching the other filters
+    #[arg(long, global = true)]
+    latest: bool,
+    /// Select the puzzle released today, in the UTC-5 timezone puzzles are released in
+    #[arg(long, conflicts_with_all = ["year", "day", "latest"], global = true)]
+    today: bool,
+    /// Leave out a year, a day or a part, written as 2020, 2020/11 or 2020/11/2
+    #[arg(long, value_name = "YEAR[/DAY[/PART]]", global = true)]
+    skip: Vec<Skip>,
+    /// Only select days tagged with this technique, can be repeated to select any of several
+    #[arg(long = "tag", value_name = "TAG", value_enum, global = true)]
+    tags: Vec<Tag>,
+    /// Run each solution repeatedly and report timing statistics
+    #[arg(long, global = true)]
+    bench: bool,
+    /// Untimed runs executed before measuring, in bench mode
+    #[arg(long, default_value_t = 3, requires = "bench", global = true)]
+    warmup: u32,
+    /// Timed runs executed for each solution, in bench mode
+    #[arg(long, default_value_t = 10, requires = "bench", global = true)]
+    runs: u32,
+    /// Stop warming up and measuring a solution after this many seconds, even if fewer runs were executed
+    #[arg(long, value_parser = parse_seconds, requires = "bench", global = true)]
+    budget: Option<Duration>,
+    /// Format used to report results
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Human, global = true)]
+    output: OutputFormat,
+    /// Puzzle input file to use instead of the usual lookup, or `-` to read it from stdin
+    #[arg(short, long, global = true)]
+    input: Option<PathBuf>,
+    /// Run the solutions against the registered example inputs instead of the real ones
+    #[arg(long, conflicts_with = "input", global = true)]
+    examples: bool,
+    /// Number of solutions executed in parallel
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..), global = true)]
+    jobs: u16,
+    /// Run every solution in a separate, resource-limited child process
+    #[arg(long, conflicts_with = "bench", global = true)]
+    isolate: bool,
+    /// Seconds of wall time after which an isolated solution is killed
+    #[arg(long, default_value = "60", value_parser = parse_seconds, requires = "isolate", global = true)]
+    timeout: Duration,
+    /// Address space limit of an isolated solution, in MiB. Crashes from a segmentation fault or a
+    /// SIGKILL are then reported as running out of memory, which is their likely cause
+    #[arg(long, requires = "isolate", global = true, value_parser = clap::value_parser!(u64).range(1..=MAX_MEMORY_LIMIT_MIB))]
+    memory_limit: Option<u64>,
+    /// Seconds of cpu time after which an isolated solution is killed
+    #[arg(long, requires = "isolate", global = true)]
+    cpu_limit: Option<u64>,
+    /// Override a parameter of the selected solutions, as key=value
+    #[arg(long = "param", value_name = "KEY=VALUE", value_parser = params::parse_param, global = true)]
+    params: Vec<(String, String)>,
+    /// Input profile whose inputs and expected answers are used instead of the default ones
+    #[arg(long, value_name = "NAME", global = true)]
+    profile: Option<String>,
+    /// Write the answers of solutions without an expected answer to the answers file
+    #[arg(long, conflicts_with_all = ["input", "examples"], global = true)]
+    record: bool,
+    /// Run the named variant of the selected parts instead of their default implementation
+    #[arg(long, value_name = "NAME", global = true)]
+    variant: Option<String>,
+    /// Run every variant of the selected parts, checking that they agree and comparing their timings
+    #[arg(long, conflicts_with = "variant", global = true)]
+    all_variants: bool,
+    /// Rerun the selected solutions whenever their inputs, examples or sources change
+    #[arg(long, conflicts_with_all = ["record", "save_baseline", "compare"])]
+    watch: bool,
+    /// Save the timings of this run as a named baseline
+    #[arg(long, value_name = "NAME")]
+    save_baseline: Option<String>,
+    /// Compare the timings of this run against a named baseline
+    #[arg(long, value_name = "NAME")]
+    compare: Option<String>,
+    /// Slowdown, in percent, above which a solution is reported as a regression
+    #[arg(long, default_value_t = 10.0, requires = "compare")]
+    threshold: f64,
+    #[arg(long = runner::CHILD_ARGUMENT, hide = true)]
+    isolated_child: Option<String>,
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    let seconds = str::parse::<f64>(value).map_err(|e| e.to_string())?;
+    return Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string());
 }
 
-fn prettify_duration(value: Duration) -> String {
-    if value.as_micros() < 2500 {
-        format!("{}μs", value.as_micros())
-    } else if value.as_millis() < 2500 {
-        format!("{}ms", value.as_millis())
-    } else {
-        format!("{}s", value.as_secs())
+/// Year and day filters, which --today replaces with the day of the puzzle released today
+fn selected_days(args: &Arguments) -> (Option<NumberSet>, Option<NumberSet>) {
+    if !args.today {
+        return (args.year.clone(), args.day.clone());
     }
+
+    return match selection::today(SystemTime::now()) {
+        Ok((year, day)) => (Some(NumberSet::single(year)), Some(NumberSet::single(day))),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
 }
 
-fn main() {
-    let args = Arguments::parse();
+fn select(args: &Arguments) -> Selection {
+    let (years, days) = selected_days(args);
+    let criteria = Criteria {
+        years,
+        days,
+        parts: args.part.clone(),
+        latest: args.latest,
+        tags: args.tags.clone(),
+        skip: args.skip.clone(),
+        variant: args.variant.clone(),
+        all_variants: args.all_variants,
+    };
+
+    return match selection::select(&criteria) {
+        Ok(selection) => selection,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    };
+}
 
-    if args.part.is_some() && args.day.is_none() {
-        eprintln!("must specify day");
+fn load_environment(args: &Arguments) -> Environment {
+    return match Environment::load(args.input.as_deref(), args.profile.clone()) {
+        Ok(environment) => environment,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    };
+}
+
+fn run_settings(args: &Arguments, cli_params: &Params) -> RunSettings {
+    return RunSettings {
+        execution: ExecutionOptions {
+            bench: match args.bench {
+                true => Some(BenchOptions {
+                    warmup: args.warmup,
+                    runs: args.runs,
+                    budget: args.budget,
+                }),
+                false => None,
+            },
+            sandbox: match args.isolate {
+                true => Some(SandboxOptions {
+                    timeout: args.timeout,
+                    memory_limit: args.memory_limit.map(|mib| mib * 1024 * 1024),
+                    cpu_limit: args.cpu_limit,
+                }),
+                false => None,
+            },
+        },
+        jobs: args.jobs as usize,
+        output: args.output,
+        params: cli_params.clone(),
+        use_examples: args.examples,
+        record: args.record,
+        compare_variants: args.all_variants,
+        save_baseline: args.save_baseline.clone(),
+        compare: args.compare.clone().map(|name| (name, args.threshold)),
+    };
+}
+
+/// Runs the wanted solutions of a selection, or watches them with --watch, handing every record to
+/// `observe` and showing it unless `quiet`. Returns whether a solution crashed, regressed or
+/// disagreed with another variant
+fn run_selection(
+    args: &Arguments,
+    cli_params: &Params,
+    selection: &Selection,
+    environment: &mut Environment,
+    quiet: bool,
+    observe: impl FnMut(&Record),
+) -> bool {
+    let settings = run_settings(args, cli_params);
+
+    if args.watch {
+        let mut days: Vec<(i32, i32)> = selection
+            .wanted
+            .iter()
+            .map(|solution| (solution.year, solution.day))
+            .collect();
+        days.dedup();
+        let sources = days
+            .iter()
+            .map(|(year, day)| scaffold::problem_path(*year, *day))
+            .collect::<Vec<PathBuf>>();
+
+        let Err(e) = watch::watch(&selection.wanted, environment, &settings, &sources);
+        eprintln!("{:#}", e);
         process::exit(1);
     }
-    if args.day.is_some() && args.year.is_none() {
-        eprintln!("must specify year");
+
+    let outcome =
+        match runner::run_selection(&selection.wanted, environment, &settings, quiet, observe) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                eprintln!("{:#}", e);
+                process::exit(1);
+            }
+        };
+    if let (Some(name), Some(path)) = (&args.save_baseline, &outcome.saved_baseline) {
+        eprintln!("saved baseline {} to {}", name, path.display());
+    }
+    if let Some((recorded, path)) = &outcome.recorded {
+        eprintln!("recorded {} answers to {}", recorded, path.display());
+    }
+
+    return outcome.failed;
+}
+
+fn run_solutions(args: &Arguments, cli_params: &Params) {
+    let selection = select(args);
+    let mut environment = load_environment(args);
+
+    if run_selection(
+        args,
+        cli_params,
+        &selection,
+        &mut environment,
+        false,
+        |_| {},
+    ) {
         process::exit(1);
     }
+}
 
-    let matches_year = filter_from_option(args.year);
-    let matches_day = filter_from_option(args.day);
-    let matches_part = filter_from_option(args.part);
+fn run_verify(args: &Arguments, cli_params: &Params) {
+    let selection = select(args);
+    let mut environment = load_environment(args);
 
-    let wanted_solutions = SOLUTIONS
-        .iter()
-        .filter(|solution| {
-            matches_year(solution.year) && matches_day(solution.day) && matches_part(solution.part)
-        })
-        .collect::<Vec<&Solution>>();
-
-    if wanted_solutions.is_empty() {
-        eprintln!("no solutions found for the given criteria.");
-        process::exit(1)
+    let mut verification = Verification::default();
+    verification.check_registry(&SOLUTIONS, &selection.wanted, &environment.expected_answers);
+    if !args.examples {
+        verification.check_inputs(&selection.wanted, &environment.input_resolver);
     }
 
-    let mut total_time = Duration::new(0, 0);
-    wanted_solutions.iter().for_each(|solution| {
-        let input_file_path = format!("./../puzzle-inputs/{}/{}.txt", solution.year, solution.day);
-        let input_file = Path::new(&input_file_path);
-        let puzzle_input: String = match fs::read_to_string(input_file) {
-            Ok(input) => input,
-            Err(e) => {
-                eprintln!(
-                    "{}/{} P{} | could not read puzzle input: {}",
-                    solution.year, solution.day, solution.part, e
-                );
-                return;
-            }
-        };
+    let failed = run_selection(
+        args,
+        cli_params,
+        &selection,
+        &mut environment,
+        false,
+        |record| verification.check_record(record),
+    );
+    verification.print();
 
-        let start = SystemTime::now();
-        let result = (solution.solution)(puzzle_input);
-        let time_taken = match start.elapsed() {
-            Ok(time_taken) => time_taken,
-            Err(e) => {
-                eprintln!(
-                    "{}/{} P{} | could not compute elapsed time: {}",
-                    solution.year, solution.day, solution.part, e
+    if failed || !verification.passed() {
+        process::exit(1);
+    }
+}
+
+fn run_new(args: &Arguments) {
+    let (years, days) = selected_days(args);
+    let year = years.as_ref().and_then(NumberSet::as_single);
+    let day = days.as_ref().and_then(NumberSet::as_single);
+    let (year, day) = match (year, day) {
+        (Some(year), Some(day)) => (year, day),
+        _ => {
+            eprintln!("must specify a single year and day, or --today");
+            process::exit(1);
+        }
+    };
+
+    match scaffold::scaffold(year, day) {
+        Ok(scaffolded) => {
+            println!("created {}", scaffolded.path.display());
+            if let Some(feature) = scaffolded.added_feature {
+                println!(
+                    "added the {} feature to Cargo.toml, enabled by default",
+                    feature
                 );
-                return;
-            }
-        };
-        total_time += time_taken;
-
-        let correctness_indicator = match (&result, solution.expected) {
-            (Ok(result), Some(expected)) => {
-                if result == expected {
-                    String::from("✅")
-                } else {
-                    String::from("❌")
-                }
             }
-            (_, _) => String::from("❓"),
-        };
+        }
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    }
+}
 
-        let expected_string: Option<String> = match (&result, solution.expected) {
-            (Ok(result), Some(expected)) => {
-                if result != expected {
-                    Some(format!(" (expected {})", expected))
-                } else {
-                    None
-                }
-            }
-            (_, _) => None,
-        };
+fn run_list(args: &Arguments) {
+    metadata::print_list(&select(args).selected);
+}
 
-        println!(
-            "{} | {}/{} P{}: {:6} | {}{}",
-            correctness_indicator,
-            solution.year,
-            solution.day,
-            solution.part,
-            prettify_duration(time_taken),
-            match result {
-                Ok(s) => s,
-                Err(e) => format!("error computing solution: {}", e),
-            },
-            expected_string.unwrap_or_default()
-        );
-    });
+fn run_status(args: &Arguments) {
+    let selection = select(args);
+    let environment = load_environment(args);
+
+    let last_timings = match environment.require_data_dir().and_then(Baseline::load_last) {
+        Ok(last_timings) => last_timings,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    };
+    let mut years = selection
+        .wanted
+        .iter()
+        .map(|solution| solution.year)
+        .collect::<Vec<i32>>();
+    years.dedup();
+
+    let registered = SOLUTIONS.iter().collect::<Vec<&Solution>>();
+    print!(
+        "{}",
+        Calendar::new(
+            &registered,
+            &years,
+            &environment.expected_answers,
+            &environment.input_resolver,
+            &last_timings
+        )
+    );
+}
+
+fn run_report(args: &Arguments, cli_params: &Params, report_args: &ReportArguments) {
+    let selection = select(args);
+    let mut environment = load_environment(args);
+
+    if let Some(readme) = &report_args.readme {
+        if let Err(e) = report::check_readme(readme) {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    }
+
+    // a report written to standard output replaces the usual output, which otherwise shows progress
+    let to_stdout = report_args.file.is_none() && report_args.readme.is_none();
+    let mut report = Report::new(report_args.mask_answers);
+    let failed = run_selection(
+        args,
+        cli_params,
+        &selection,
+        &mut environment,
+        to_stdout,
+        |record| report.add(record),
+    );
+
+    let rendered = report.render(report_args.format);
+    let written = match (&report_args.file, &report_args.readme) {
+        (Some(path), _) => fs::write(path, &rendered)
+            .with_context(|| format!("could not write report {}", path.display()))
+            .map(|_| Some(path)),
+        (None, Some(path)) => report::update_readme(path, &rendered).map(|_| Some(path)),
+        (None, None) => {
+            print!("{}", rendered);
+            Ok(None)
+        }
+    };
+    match written {
+        Ok(Some(path)) => eprintln!("wrote report to {}", path.display()),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "serve")]
+fn run_serve(args: &Arguments, serve_args: &ServeArguments) {
+    let options = ServeOptions {
+        port: serve_args.port,
+        workers: match serve_args.workers {
+            Some(workers) => workers as usize,
+            None => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        },
+        timeout: serve_args.request_timeout,
+    };
+
+    // every request gives its own variant and parameters, so the whole selection is served
+    if let Err(e) = serve::serve(&select(args).selected, &options) {
+        eprintln!("{:#}", e);
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let args = Arguments::parse();
+    let cli_params = args.params.iter().cloned().collect::<Params>();
+
+    if let Some(child_argument) = &args.isolated_child {
+        runner::run_isolated_child(child_argument, &cli_params);
+    }
 
-    if wanted_solutions.len() > 1 {
-        println!("\n🕑 | {}", prettify_duration(total_time));
+    match &args.command {
+        None => run_solutions(&args, &cli_params),
+        Some(Command::Verify) => run_verify(&args, &cli_params),
+        Some(Command::New) => run_new(&args),
+        Some(Command::List) => run_list(&args),
+        Some(Command::Status) => run_status(&args),
+        Some(Command::Report(report_args)) => run_report(&args, &cli_params, report_args),
+        #[cfg(feature = "serve")]
+        Some(Command::Serve(serve_args)) => run_serve(&args, serve_args),
     }
 }
@@ -0,0 +1,102 @@
+use std::fmt;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::problems::Solution;
+
+/// Technique a solution relies on, used to select related solutions
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tag {
+    /// Traversal of nodes and edges
+    Graph,
+    /// Two-dimensional maps, usually walked with `ext::matrix`
+    Grid,
+    /// Step by step evolution of a state
+    Simulation,
+    /// Dynamic programming
+    Dp,
+    /// Interpreter for a puzzle-defined instruction set
+    Vm,
+    /// Inputs whose parsing or validation is most of the puzzle
+    Parsing,
+    /// Lookups for numbers or ranges with a given property
+    Search,
+    /// Set operations
+    Sets,
+    /// Arithmetic and number encodings
+    Math,
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// Description of a day shared by all of its parts
+pub struct Metadata {
+    pub title: Option<&'static str>,
+    pub tags: &'static [Tag],
+    /// Free-form remarks about the approach
+    pub notes: Option<&'static str>,
+}
+
+/// Prints the metadata of every day among the solutions, with their parts, variants and parameters
+pub fn print_list(solutions: &[&Solution]) {
+    let mut days: Vec<(i32, i32)> = solutions.iter().map(|s| (s.year, s.day)).collect();
+    days.dedup();
+
+    for (year, day) in days {
+        let day_solutions = solutions
+            .iter()
+            .filter(|solution| (solution.year, solution.day) == (year, day))
+            .collect::<Vec<_>>();
+        let metadata = day_solutions[0].metadata;
+
+        let mut header = format!("{}/{}", year, day);
+        if let Some(title) = metadata.title {
+            header.push_str(&format!(" · {}", title));
+        }
+        if !metadata.tags.is_empty() {
+            let tags = metadata.tags.iter().map(Tag::to_string);
+            header.push_str(&format!(" [{}]", tags.collect::<Vec<String>>().join(", ")));
+        }
+        println!("{}", header);
+
+        let mut parts = day_solutions
+            .iter()
+            .filter(|solution| solution.is_default_variant())
+            .map(|solution| solution.part.to_string())
+            .collect::<Vec<String>>();
+        parts.dedup();
+        let mut details = vec![format!("parts {}", parts.join(", "))];
+
+        let variants = day_solutions
+            .iter()
+            .filter(|solution| !solution.is_default_variant())
+            .map(|solution| format!("P{} {}", solution.part, solution.variant))
+            .collect::<Vec<String>>();
+        if !variants.is_empty() {
+            details.push(format!("variants {}", variants.join(", ")));
+        }
+
+        let params = day_solutions[0]
+            .params
+            .iter()
+            .map(|param| format!("{}={}", param.name(), param.default()))
+            .collect::<Vec<String>>();
+        if !params.is_empty() {
+            details.push(format!("params {}", params.join(", ")));
+        }
+        println!("   | {}", details.join(" · "));
+
+        if let Some(notes) = metadata.notes {
+            println!("   | {}", notes);
+        }
+    }
+}
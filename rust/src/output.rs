@@ -0,0 +1,709 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::allocations::{prettify_bytes, AllocationStats};
+use crate::answer::Answer;
+use crate::bench::{BenchReport, Statistics};
+use crate::metadata::Tag;
+use crate::panics::SolutionPanic;
+use crate::problems::Solution;
+use crate::sandbox::SandboxError;
+use crate::timing::{prettify_duration, Measurement};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// Emoji-decorated lines meant to be read in a terminal
+    Human,
+    /// One JSON object per line
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// A JUnit XML test report
+    Junit,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Correct,
+    Wrong,
+    Unknown,
+    Error,
+    Panicked,
+    Timeout,
+    OutOfMemory,
+    Signaled,
+}
+
+impl Status {
+    pub fn indicator(&self) -> &'static str {
+        match self {
+            Status::Correct => "✅",
+            Status::Wrong => "❌",
+            Status::Unknown | Status::Error => "❓",
+            Status::Panicked => "💥",
+            Status::Timeout => "⌛",
+            Status::OutOfMemory => "💾",
+            Status::Signaled => "💀",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unknown => "unknown",
+            Status::Error => "error",
+            Status::Panicked => "panicked",
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "oom",
+            Status::Signaled => "signaled",
+        }
+    }
+
+    /// Whether the solution failed to return at all, rather than returning an answer or an error
+    pub fn crashed(&self) -> bool {
+        return matches!(
+            self,
+            Status::Panicked | Status::Timeout | Status::OutOfMemory | Status::Signaled
+        );
+    }
+
+    fn of_crash(error: &anyhow::Error) -> Option<Status> {
+        if error.is::<SolutionPanic>() {
+            return Some(Status::Panicked);
+        }
+
+        return match error.downcast_ref::<SandboxError>()? {
+            SandboxError::Timeout(_) | SandboxError::CpuLimit(_) => Some(Status::Timeout),
+            SandboxError::OutOfMemory => Some(Status::OutOfMemory),
+            SandboxError::Signal(_) => Some(Status::Signaled),
+        };
+    }
+}
+
+fn variant_name(solution: &Solution) -> Option<String> {
+    return match solution.is_default_variant() {
+        true => None,
+        false => Some(solution.variant.into()),
+    };
+}
+
+pub struct Record {
+    pub year: i32,
+    pub day: i32,
+    pub part: i32,
+    /// Name of the implementation that ran, unless it is the default one
+    pub variant: Option<String>,
+    pub example: Option<String>,
+    /// Puzzle title and techniques of the day, when registered
+    pub title: Option<&'static str>,
+    pub tags: &'static [Tag],
+    pub answer: Option<Answer>,
+    pub expected: Option<Answer>,
+    pub status: Status,
+    pub error: Option<String>,
+    pub time: Option<Measurement>,
+    /// Time taken to parse the input, for solutions that parse it separately from solving
+    pub parse: Option<Measurement>,
+    pub bench: Option<BenchReport>,
+}
+
+impl Record {
+    pub fn new(
+        solution: &Solution,
+        expected: Option<Answer>,
+        result: Result<Answer>,
+        time: Measurement,
+        bench: Option<BenchReport>,
+    ) -> Record {
+        let (answer, error, crash) = match result {
+            Ok(answer) => (Some(answer), None, None),
+            Err(e) => match Status::of_crash(&e) {
+                Some(crash) => (None, Some(e.to_string()), Some(crash)),
+                None => (None, Some(format!("error computing solution: {}", e)), None),
+            },
+        };
+        let status = match (&answer, &expected, crash) {
+            (Some(answer), Some(expected), _) if answer.matches(expected) => Status::Correct,
+            (Some(_), Some(_), _) => Status::Wrong,
+            (Some(_), None, _) => Status::Unknown,
+            (None, _, Some(crash)) => crash,
+            (None, _, None) => Status::Error,
+        };
+
+        return Record {
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            variant: variant_name(solution),
+            example: None,
+            title: solution.metadata.title,
+            tags: solution.metadata.tags,
+            answer,
+            expected,
+            status,
+            error,
+            time: Some(time),
+            parse: None,
+            bench,
+        };
+    }
+
+    pub fn not_run(solution: &Solution, expected: Option<Answer>, error: String) -> Record {
+        return Record {
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            variant: variant_name(solution),
+            example: None,
+            title: solution.metadata.title,
+            tags: solution.metadata.tags,
+            answer: None,
+            expected,
+            status: Status::Error,
+            error: Some(error),
+            time: None,
+            parse: None,
+            bench: None,
+        };
+    }
+
+    pub fn with_parse(mut self, parse: Measurement) -> Record {
+        self.parse = Some(parse);
+        return self;
+    }
+
+    pub fn with_example(mut self, example: &str) -> Record {
+        self.example = Some(example.into());
+        return self;
+    }
+
+    pub fn label(&self) -> String {
+        let mut label = format!("{}/{} P{}", self.year, self.day, self.part);
+        if let Some(variant) = &self.variant {
+            label.push_str(&format!(" ({})", variant));
+        }
+        if let Some(example) = &self.example {
+            label.push_str(&format!(" [{}]", example));
+        }
+        return label;
+    }
+}
+
+pub struct Summary {
+    pub count: usize,
+    /// Sum of the time taken by every executed solution
+    pub total: Measurement,
+    /// Wall time elapsed from the first solution starting to the last one finishing
+    pub elapsed: Duration,
+}
+
+pub trait Reporter {
+    fn report(&mut self, record: &Record);
+    fn finish(&mut self, summary: &Summary);
+}
+
+pub fn reporter(format: OutputFormat) -> Box<dyn Reporter> {
+    match format {
+        OutputFormat::Human => Box::new(HumanReporter),
+        OutputFormat::Json => Box::new(JsonReporter),
+        OutputFormat::Csv => Box::new(CsvReporter {
+            header_written: false,
+        }),
+        OutputFormat::Junit => Box::new(JunitReporter {
+            records: Vec::new(),
+        }),
+    }
+}
+
+struct HumanReporter;
+
+fn describe_allocations(allocations: &AllocationStats) -> String {
+    return format!(
+        "peak {} · {} in {} allocations",
+        prettify_bytes(allocations.peak_bytes),
+        prettify_bytes(allocations.allocated_bytes),
+        allocations.allocations
+    );
+}
+
+impl Reporter for HumanReporter {
+    fn report(&mut self, record: &Record) {
+        let time = match record.time {
+            Some(time) => time,
+            None => {
+                eprintln!(
+                    "{} | {}",
+                    record.label(),
+                    record.error.as_deref().unwrap_or_default()
+                );
+                return;
+            }
+        };
+
+        // multi-line answers are printed below the result line, where they keep their shape
+        let mut grids: Vec<(&str, &Answer)> = Vec::new();
+        let answer_string = match (&record.answer, &record.error) {
+            (Some(answer), _) if answer.is_multiline() => {
+                grids.push(("answer", answer));
+                String::from("(below)")
+            }
+            (Some(answer), _) => answer.to_string(),
+            (None, error) => error.clone().unwrap_or_default(),
+        };
+        let expected_string = match (record.status, &record.expected) {
+            (Status::Wrong, Some(expected)) if expected.is_multiline() => {
+                grids.push(("expected", expected));
+                String::from(" (expected below)")
+            }
+            (Status::Wrong, Some(expected)) => format!(" (expected {})", expected),
+            (_, _) => String::new(),
+        };
+
+        let parse_string = match record.parse {
+            Some(Measurement {
+                wall,
+                allocations: Some(allocations),
+                ..
+            }) => format!(
+                " | 📖 parsed in {} · {}",
+                prettify_duration(wall),
+                describe_allocations(&allocations)
+            ),
+            Some(parse) => format!(" | 📖 parsed in {}", prettify_duration(parse.wall)),
+            None => String::new(),
+        };
+
+        let allocations_string = match time.allocations {
+            Some(allocations) => format!(" | 🧮 {}", describe_allocations(&allocations)),
+            None => String::new(),
+        };
+
+        let title_string = match record.title {
+            Some(title) => format!(" · {}", title),
+            None => String::new(),
+        };
+
+        println!(
+            "{} | {}{}: {:6} | {}{}{}{}",
+            record.status.indicator(),
+            record.label(),
+            title_string,
+            prettify_duration(time.wall),
+            answer_string,
+            expected_string,
+            parse_string,
+            allocations_string
+        );
+
+        for (name, grid) in grids {
+            println!("   | {}:", name);
+            for line in grid.to_string().lines() {
+                println!("   |   {}", line);
+            }
+        }
+
+        if let Some(report) = &record.bench {
+            println!("   | {} runs", report.runs);
+            println!("   | wall {}", report.wall);
+            println!("   | cpu  {}", report.cpu);
+        }
+    }
+
+    fn finish(&mut self, summary: &Summary) {
+        if summary.count > 1 {
+            println!(
+                "\n🕑 | {} (cpu {}, elapsed {})",
+                prettify_duration(summary.total.wall),
+                prettify_duration(summary.total.cpu),
+                prettify_duration(summary.elapsed)
+            );
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonStatistics {
+    min_ns: u64,
+    median_ns: u64,
+    mean_ns: u64,
+    stddev_ns: u64,
+    max_ns: u64,
+}
+
+impl From<&Statistics> for JsonStatistics {
+    fn from(value: &Statistics) -> Self {
+        return JsonStatistics {
+            min_ns: nanos(value.min),
+            median_ns: nanos(value.median),
+            mean_ns: nanos(value.mean),
+            stddev_ns: nanos(value.stddev),
+            max_ns: nanos(value.max),
+        };
+    }
+}
+
+#[derive(Serialize)]
+struct JsonBench {
+    runs: usize,
+    wall: JsonStatistics,
+    cpu: JsonStatistics,
+}
+
+/// Answer as written in JSON, where numbers stay numbers and text and grids are strings
+#[derive(Serialize)]
+#[serde(untagged)]
+enum JsonAnswer {
+    Integer(i64),
+    Unsigned(u64),
+    Text(String),
+}
+
+impl From<&Answer> for JsonAnswer {
+    fn from(value: &Answer) -> Self {
+        return match value {
+            Answer::Integer(value) => JsonAnswer::Integer(*value),
+            Answer::Unsigned(value) => JsonAnswer::Unsigned(*value),
+            Answer::Text(_) | Answer::Grid(_) => JsonAnswer::Text(value.to_string()),
+        };
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    year: i32,
+    day: i32,
+    part: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variant: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    example: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[Tag]>::is_empty")]
+    tags: &'a [Tag],
+    answer: Option<JsonAnswer>,
+    expected: Option<JsonAnswer>,
+    status: Status,
+    error: Option<&'a str>,
+    duration_ns: Option<u64>,
+    cpu_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_cpu_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocated_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocations: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_peak_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_allocated_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_allocations: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bench: Option<JsonBench>,
+}
+
+fn nanos(value: Duration) -> u64 {
+    return value.as_nanos().try_into().unwrap_or(u64::MAX);
+}
+
+impl Record {
+    /// The record as the JSON object reported for it by the json output
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let allocations = self.time.and_then(|time| time.allocations);
+        let parse_allocations = self.parse.and_then(|parse| parse.allocations);
+        let json_record = JsonRecord {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+            variant: self.variant.as_deref(),
+            example: self.example.as_deref(),
+            title: self.title,
+            tags: self.tags,
+            answer: self.answer.as_ref().map(JsonAnswer::from),
+            expected: self.expected.as_ref().map(JsonAnswer::from),
+            status: self.status,
+            error: self.error.as_deref(),
+            duration_ns: self.time.map(|time| nanos(time.wall)),
+            cpu_ns: self.time.map(|time| nanos(time.cpu)),
+            parse_ns: self.parse.map(|parse| nanos(parse.wall)),
+            parse_cpu_ns: self.parse.map(|parse| nanos(parse.cpu)),
+            peak_bytes: allocations.map(|allocations| allocations.peak_bytes),
+            allocated_bytes: allocations.map(|allocations| allocations.allocated_bytes),
+            allocations: allocations.map(|allocations| allocations.allocations),
+            parse_peak_bytes: parse_allocations.map(|allocations| allocations.peak_bytes),
+            parse_allocated_bytes: parse_allocations.map(|allocations| allocations.allocated_bytes),
+            parse_allocations: parse_allocations.map(|allocations| allocations.allocations),
+            bench: self.bench.as_ref().map(|report| JsonBench {
+                runs: report.runs,
+                wall: (&report.wall).into(),
+                cpu: (&report.cpu).into(),
+            }),
+        };
+
+        return serde_json::to_string(&json_record);
+    }
+}
+
+struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&mut self, record: &Record) {
+        match record.to_json() {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!("{} | could not serialize record: {}", record.label(), e),
+        }
+    }
+
+    fn finish(&mut self, _: &Summary) {}
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+
+    return value.into();
+}
+
+struct CsvReporter {
+    header_written: bool,
+}
+
+impl Reporter for CsvReporter {
+    fn report(&mut self, record: &Record) {
+        if !self.header_written {
+            println!(
+                "year,day,part,variant,example,title,tags,answer,expected,status,error,duration_ns,cpu_ns,parse_ns,parse_cpu_ns,peak_bytes,allocated_bytes,allocations,parse_peak_bytes,parse_allocated_bytes,parse_allocations"
+            );
+            self.header_written = true;
+        }
+
+        let allocations = record.time.and_then(|time| time.allocations);
+        let parse_allocations = record.parse.and_then(|parse| parse.allocations);
+        println!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            record.year,
+            record.day,
+            record.part,
+            csv_field(record.variant.as_deref().unwrap_or_default()),
+            csv_field(record.example.as_deref().unwrap_or_default()),
+            csv_field(record.title.unwrap_or_default()),
+            csv_field(
+                &record
+                    .tags
+                    .iter()
+                    .map(Tag::to_string)
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
+            csv_field(
+                &record
+                    .answer
+                    .as_ref()
+                    .map(Answer::to_string)
+                    .unwrap_or_default()
+            ),
+            csv_field(
+                &record
+                    .expected
+                    .as_ref()
+                    .map(Answer::to_string)
+                    .unwrap_or_default()
+            ),
+            record.status.name(),
+            csv_field(record.error.as_deref().unwrap_or_default()),
+            record
+                .time
+                .map(|time| nanos(time.wall).to_string())
+                .unwrap_or_default(),
+            record
+                .time
+                .map(|time| nanos(time.cpu).to_string())
+                .unwrap_or_default(),
+            record
+                .parse
+                .map(|parse| nanos(parse.wall).to_string())
+                .unwrap_or_default(),
+            record
+                .parse
+                .map(|parse| nanos(parse.cpu).to_string())
+                .unwrap_or_default(),
+            allocations
+                .map(|allocations| allocations.peak_bytes.to_string())
+                .unwrap_or_default(),
+            allocations
+                .map(|allocations| allocations.allocated_bytes.to_string())
+                .unwrap_or_default(),
+            allocations
+                .map(|allocations| allocations.allocations.to_string())
+                .unwrap_or_default(),
+            parse_allocations
+                .map(|allocations| allocations.peak_bytes.to_string())
+                .unwrap_or_default(),
+            parse_allocations
+                .map(|allocations| allocations.allocated_bytes.to_string())
+                .unwrap_or_default(),
+            parse_allocations
+                .map(|allocations| allocations.allocations.to_string())
+                .unwrap_or_default(),
+        );
+    }
+
+    fn finish(&mut self, _: &Summary) {}
+}
+
+pub fn xml_escape(value: &str) -> String {
+    return value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;");
+}
+
+/// Escapes a value written in an attribute, where parsers would otherwise turn line breaks into spaces
+fn xml_attribute_escape(value: &str) -> String {
+    return xml_escape(value)
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;");
+}
+
+struct JunitReporter {
+    records: Vec<JunitCase>,
+}
+
+struct JunitCase {
+    year: i32,
+    name: String,
+    status: Status,
+    answer: Option<String>,
+    expected: Option<String>,
+    error: Option<String>,
+    seconds: f64,
+}
+
+impl Reporter for JunitReporter {
+    fn report(&mut self, record: &Record) {
+        self.records.push(JunitCase {
+            year: record.year,
+            name: {
+                let mut name = format!("day {} part {}", record.day, record.part);
+                if let Some(variant) = &record.variant {
+                    name.push_str(&format!(" variant {}", variant));
+                }
+                if let Some(example) = &record.example {
+                    name.push_str(&format!(" example {}", example));
+                }
+                name
+            },
+            status: record.status,
+            answer: record.answer.as_ref().map(Answer::to_string),
+            expected: record.expected.as_ref().map(Answer::to_string),
+            error: record.error.clone(),
+            seconds: record
+                .time
+                .map(|time| time.wall.as_secs_f64())
+                .unwrap_or(0.0),
+        });
+    }
+
+    fn finish(&mut self, summary: &Summary) {
+        let count_of = |status: Status| self.records.iter().filter(|r| r.status == status).count();
+
+        println!(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        println!(
+            r#"<testsuites name="aoc" tests="{}" failures="{}" errors="{}" time="{:.6}">"#,
+            self.records.len(),
+            count_of(Status::Wrong),
+            self.records
+                .iter()
+                .filter(|r| r.status == Status::Error || r.status.crashed())
+                .count(),
+            summary.elapsed.as_secs_f64()
+        );
+
+        let mut years = self.records.iter().map(|r| r.year).collect::<Vec<i32>>();
+        years.dedup();
+        for year in years {
+            let cases = self
+                .records
+                .iter()
+                .filter(|r| r.year == year)
+                .collect::<Vec<&JunitCase>>();
+            let failures = cases.iter().filter(|c| c.status == Status::Wrong).count();
+            let errors = cases
+                .iter()
+                .filter(|c| c.status == Status::Error || c.status.crashed())
+                .count();
+            let skipped = cases.iter().filter(|c| c.status == Status::Unknown).count();
+            let time: f64 = cases.iter().map(|c| c.seconds).sum();
+
+            println!(
+                r#"  <testsuite name="{}" tests="{}" failures="{}" errors="{}" skipped="{}" time="{:.6}">"#,
+                year,
+                cases.len(),
+                failures,
+                errors,
+                skipped,
+                time
+            );
+            for case in cases {
+                let open_tag = format!(
+                    r#"    <testcase classname="aoc.{}" name="{}" time="{:.6}""#,
+                    year,
+                    xml_attribute_escape(&case.name),
+                    case.seconds
+                );
+                let answer = case.answer.as_deref().unwrap_or_default();
+                match case.status {
+                    Status::Correct => println!("{} />", open_tag),
+                    Status::Wrong => {
+                        println!("{}>", open_tag);
+                        println!(
+                            r#"      <failure message="{}" type="wrong answer" />"#,
+                            xml_attribute_escape(&format!(
+                                "got {} but expected {}",
+                                answer,
+                                case.expected.as_deref().unwrap_or_default()
+                            ))
+                        );
+                        println!("    </testcase>");
+                    }
+                    Status::Unknown => {
+                        println!("{}>", open_tag);
+                        println!(
+                            r#"      <skipped message="{}" />"#,
+                            xml_attribute_escape(&format!("no expected answer, got {}", answer))
+                        );
+                        println!("    </testcase>");
+                    }
+                    Status::Error
+                    | Status::Panicked
+                    | Status::Timeout
+                    | Status::OutOfMemory
+                    | Status::Signaled => {
+                        println!("{}>", open_tag);
+                        println!(
+                            r#"      <error message="{}" />"#,
+                            xml_attribute_escape(case.error.as_deref().unwrap_or_default())
+                        );
+                        println!("    </testcase>");
+                    }
+                }
+            }
+            println!("  </testsuite>");
+        }
+
+        println!("</testsuites>");
+    }
+}
@@ -0,0 +1,79 @@
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use anyhow::Result;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+#[error("solution panicked at {location}: {message}")]
+pub struct SolutionPanic {
+    pub message: String,
+    pub location: String,
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static CAUGHT: RefCell<Option<SolutionPanic>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook that silently records panics raised inside `call_guarded`,
+/// deferring to the previous hook for every other panic. Only the first call installs it
+pub fn install_hook() {
+    INSTALL_HOOK.call_once(install_recording_hook);
+}
+
+fn install_recording_hook() {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !CATCHING.get() {
+            previous_hook(info);
+            return;
+        }
+
+        let payload = info.payload();
+        let message = match (
+            payload.downcast_ref::<&str>(),
+            payload.downcast_ref::<String>(),
+        ) {
+            (Some(message), _) => message.to_string(),
+            (_, Some(message)) => message.clone(),
+            (None, None) => String::from("unknown panic payload"),
+        };
+        let location = match info.location() {
+            Some(location) => format!(
+                "{}:{}:{}",
+                // solution modules are included through absolute paths by the generated registry
+                location
+                    .file()
+                    .strip_prefix(concat!(env!("CARGO_MANIFEST_DIR"), "/"))
+                    .unwrap_or(location.file()),
+                location.line(),
+                location.column()
+            ),
+            None => String::from("unknown location"),
+        };
+
+        CAUGHT.set(Some(SolutionPanic { message, location }));
+    }));
+}
+
+/// Calls a solution, or one of its steps, turning a panic into an error carrying its message and location
+pub fn call_guarded<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    CATCHING.set(true);
+    let outcome = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(false);
+
+    return match outcome {
+        Ok(result) => result,
+        Err(_) => Err(CAUGHT
+            .take()
+            .unwrap_or(SolutionPanic {
+                message: String::from("unknown panic"),
+                location: String::from("unknown location"),
+            })
+            .into()),
+    };
+}
@@ -0,0 +1,127 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::problems::Solution;
+
+/// Parameter overrides, by name, as given on the command line or in an examples manifest
+pub type Params = BTreeMap<String, String>;
+
+thread_local! {
+    static OVERRIDES: RefCell<Params> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Named constant of a solution that can be overridden at runtime, such as a value that differs
+/// between the examples and the real input. Its default is written the way it would be overridden
+pub struct Param<T> {
+    name: &'static str,
+    default: &'static str,
+    value_type: PhantomData<fn() -> T>,
+}
+
+impl<T: FromStr> Param<T>
+where
+    T::Err: Display,
+{
+    pub const fn new(name: &'static str, default: &'static str) -> Param<T> {
+        return Param {
+            name,
+            default,
+            value_type: PhantomData,
+        };
+    }
+
+    fn parse(&self, raw: &str) -> Result<T> {
+        return raw
+            .parse::<T>()
+            .map_err(|e| anyhow!("invalid value {:?} for parameter {}: {}", raw, self.name, e));
+    }
+
+    /// Value of the parameter for the solution being run
+    pub fn get(&self) -> Result<T> {
+        return OVERRIDES.with_borrow(|overrides| match overrides.get(self.name) {
+            Some(raw) => self.parse(raw),
+            None => self.parse(self.default),
+        });
+    }
+}
+
+/// Parameter as listed in the registry, with its value type erased
+pub trait DeclaredParam: Sync {
+    fn name(&self) -> &'static str;
+    fn default(&self) -> &'static str;
+    fn check(&self, raw: &str) -> Result<()>;
+}
+
+impl<T: FromStr> DeclaredParam for Param<T>
+where
+    T::Err: Display,
+{
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn default(&self) -> &'static str {
+        return self.default;
+    }
+
+    fn check(&self, raw: &str) -> Result<()> {
+        return self.parse(raw).map(|_| ());
+    }
+}
+
+/// Parses a `key=value` command line parameter
+pub fn parse_param(argument: &str) -> Result<(String, String), String> {
+    return match argument.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.into(), value.into())),
+        _ => Err(format!("expected key=value, found {}", argument)),
+    };
+}
+
+/// Checks that every parameter is declared by at least one of the solutions and is valid for each
+/// solution declaring it
+pub fn check(solutions: &[&Solution], params: &Params) -> Result<()> {
+    for (name, raw) in params.iter() {
+        let mut declared = false;
+        for solution in solutions.iter() {
+            if let Some(param) = solution.params.iter().find(|param| param.name() == name) {
+                declared = true;
+                param.check(raw)?;
+            }
+        }
+
+        if !declared {
+            let known = solutions
+                .iter()
+                .flat_map(|solution| solution.params.iter().map(|param| param.name()))
+                .collect::<BTreeSet<&str>>()
+                .into_iter()
+                .collect::<Vec<&str>>();
+            match known.is_empty() {
+                true => bail!(
+                    "unknown parameter {}, the selected solutions take none",
+                    name
+                ),
+                false => bail!(
+                    "unknown parameter {}, the selected solutions take: {}",
+                    name,
+                    known.join(", ")
+                ),
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+/// Runs `f` with the given parameter overrides visible to the solutions it calls
+pub fn with_params<T>(params: &Params, f: impl FnOnce() -> T) -> T {
+    let previous = OVERRIDES.replace(params.clone());
+    let result = f();
+    OVERRIDES.set(previous);
+    return result;
+}
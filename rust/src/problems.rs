@@ -1,182 +1,150 @@
-use anyhow::Result;
+use std::sync::LazyLock;
 
-use crate::year_2020;
+use crate::answer::Answer;
+use crate::day::Solver;
+use crate::metadata::Metadata;
+use crate::params::DeclaredParam;
 
 pub struct Solution {
     pub year: i32,
     pub day: i32,
     pub part: i32,
-    pub solution: fn(String) -> Result<String>,
-    pub expected: Option<&'static str>,
+    /// Name of the implementation, for parts solved in more than one way
+    pub variant: &'static str,
+    pub solution: Solver,
+    pub expected: Option<Answer>,
+    pub params: &'static [&'static dyn DeclaredParam],
+    pub metadata: &'static Metadata,
 }
 
-pub static SOLUTIONS: &[Solution] = &[
-    Solution {
-        year: 2020,
-        day: 1,
-        part: 1,
-        solution: year_2020::problem_1::part_1,
-        expected: Some("440979"),
-    },
-    Solution {
-        year: 2020,
-        day: 1,
-        part: 2,
-        solution: year_2020::problem_1::part_2,
-        expected: Some("82498112"),
-    },
-    Solution {
-        year: 2020,
-        day: 2,
-        part: 1,
-        solution: year_2020::problem_2::part_1,
-        expected: Some("517"),
-    },
-    Solution {
-        year: 2020,
-        day: 2,
-        part: 2,
-        solution: year_2020::problem_2::part_2,
-        expected: Some("284"),
-    },
-    Solution {
-        year: 2020,
-        day: 3,
-        part: 1,
-        solution: year_2020::problem_3::part_1,
-        expected: Some("211"),
-    },
-    Solution {
-        year: 2020,
-        day: 3,
-        part: 2,
-        solution: year_2020::problem_3::part_2,
-        expected: Some("3584591857"),
-    },
-    Solution {
-        year: 2020,
-        day: 4,
-        part: 1,
-        solution: year_2020::problem_4::part_1,
-        expected: Some("230"),
-    },
-    Solution {
-        year: 2020,
-        day: 4,
-        part: 2,
-        solution: year_2020::problem_4::part_2,
-        expected: Some("156"),
-    },
-    Solution {
-        year: 2020,
-        day: 5,
-        part: 1,
-        solution: year_2020::problem_5::part_1,
-        expected: Some("994"),
-    },
-    Solution {
-        year: 2020,
-        day: 5,
-        part: 2,
-        solution: year_2020::problem_5::part_2,
-        expected: Some("741"),
-    },
-    Solution {
-        year: 2020,
-        day: 6,
-        part: 1,
-        solution: year_2020::problem_6::part_1,
-        expected: Some("6714"),
-    },
-    Solution {
-        year: 2020,
-        day: 6,
-        part: 2,
-        solution: year_2020::problem_6::part_2,
-        expected: Some("3435"),
-    },
-    Solution {
-        year: 2020,
-        day: 7,
-        part: 1,
-        solution: year_2020::problem_7::part_1,
-        expected: Some("139"),
-    },
-    Solution {
-        year: 2020,
-        day: 7,
-        part: 2,
-        solution: year_2020::problem_7::part_2,
-        expected: Some("58175"),
-    },
-    Solution {
-        year: 2020,
-        day: 8,
-        part: 1,
-        solution: year_2020::problem_8::part_1,
-        expected: Some("1475"),
-    },
-    Solution {
-        year: 2020,
-        day: 8,
-        part: 2,
-        solution: year_2020::problem_8::part_2,
-        expected: Some("1270"),
-    },
-    Solution {
-        year: 2020,
-        day: 9,
-        part: 1,
-        solution: year_2020::problem_9::part_1,
-        expected: Some("27911108"),
-    },
-    Solution {
-        year: 2020,
-        day: 9,
-        part: 2,
-        solution: year_2020::problem_9::part_2,
-        expected: Some("4023754"),
-    },
-    Solution {
-        year: 2020,
-        day: 10,
-        part: 1,
-        solution: year_2020::problem_10::part_1,
-        expected: Some("2400"),
-    },
-    Solution {
-        year: 2020,
-        day: 10,
-        part: 2,
-        solution: year_2020::problem_10::part_2,
-        expected: Some("338510590509056"),
-    },
-    Solution {
-        year: 2020,
-        day: 11,
-        part: 1,
-        solution: year_2020::problem_11::part_1,
-        expected: Some("2438"),
-    },
-    Solution {
-        year: 2020,
-        day: 11,
-        part: 2,
-        solution: year_2020::problem_11::part_2,
-        expected: Some("2174"),
-    },
-    Solution {
-        year: 2020,
-        day: 12,
-        part: 1,
-        solution: year_2020::problem_12::part_1,
-        expected: Some("2297"),
-    },
-    Solution {
-        year: 2020,
-        day: 12,
-        part: 2,
-        solution: year_2020::problem_12::part_2,
-        expected: Some("89984"),
-    },
-];
+// Defines `registered`, which gathers the solutions of every `src/year_*/problem_*.rs` file
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+pub static SOLUTIONS: LazyLock<Vec<Solution>> = LazyLock::new(registered);
+
+/// Variant of the implementations registered without a name
+pub const DEFAULT_VARIANT: &str = "default";
+
+impl Solution {
+    pub fn is_default_variant(&self) -> bool {
+        return self.variant == DEFAULT_VARIANT;
+    }
+}
+
+pub fn find(year: i32, day: i32, part: i32) -> Option<&'static Solution> {
+    return SOLUTIONS.iter().find(|solution| {
+        (solution.year, solution.day, solution.part) == (year, day, part)
+            && solution.is_default_variant()
+    });
+}
+
+/// Registers the parts solved by the enclosing `src/year_*/problem_*.rs` file, whose path gives their
+/// year and day. The first argument is the type implementing `Day`, or `raw` for `part_N` functions
+/// taking the raw input, followed by each part and, when known, its expected answer. Optionally
+/// follow the puzzle title, the techniques used and notes on the approach, other named
+/// implementations of the parts, which take the same input as the part they replace, and the
+/// parameters the solution reads:
+///
+/// ```ignore
+/// solution!(
+///     Problem9,
+///     part_1 = 27911108,
+///     part_2;
+///     title = "Encoding Error";
+///     tags = [Search];
+///     variants = [part_2(window = sliding_window)];
+///     params = [PREAMBLE_LEN]
+/// );
+/// ```
+#[macro_export]
+macro_rules! solution {
+    (@number part_1) => {
+        1
+    };
+    (@number part_2) => {
+        2
+    };
+    (@solver raw, $part:ident) => {
+        $crate::day::Solver::Raw($part)
+    };
+    (@solver $day:ident, $part:ident) => {
+        $crate::day::Solver::$part::<$day>()
+    };
+    (@variant raw, $function:ident) => {
+        $crate::day::Solver::Raw($function)
+    };
+    (@variant $day:ident, $function:ident) => {
+        $crate::day::Solver::Parsed {
+            parse: $crate::day::parse_erased::<$day>,
+            solve: |parsed| $function($crate::day::downcast::<$day>(parsed)?),
+        }
+    };
+    (@variants $solutions:ident, $year:ident, $day:ident, $solver:ident, $part:ident, $($variant:ident = $function:ident),+) => {
+        let expected = $solutions
+            .iter()
+            .find(|solution| solution.part == $crate::solution!(@number $part))
+            .and_then(|solution| solution.expected.clone());
+        $($solutions.push($crate::problems::Solution {
+            year: $year,
+            day: $day,
+            part: $crate::solution!(@number $part),
+            variant: stringify!($variant),
+            solution: $crate::solution!(@variant $solver, $function),
+            expected: expected.clone(),
+            params: PARAMS,
+            metadata: &METADATA,
+        });)+
+    };
+    (@optional) => {
+        None
+    };
+    (@optional $value:literal) => {
+        Some($value)
+    };
+    (@expected) => {
+        None
+    };
+    (@expected $expected:expr) => {
+        Some($crate::answer::Answer::expected($expected))
+    };
+    (
+        $solver:ident $(, $part:ident $(= $expected:expr)?)+ $(,)?
+        $(; title = $title:literal)?
+        $(; tags = [$($tag:ident),* $(,)?])?
+        $(; notes = $notes:literal)?
+        $(; variants = [$($variant_part:ident($($variant:ident = $function:ident),+ $(,)?)),* $(,)?])?
+        $(; params = [$($param:ident),* $(,)?])?
+    ) => {
+        // registering a part twice defines the same variant twice, failing the build
+        #[allow(dead_code, non_camel_case_types)]
+        enum RegisteredParts {
+            $($part),+
+        }
+
+        static PARAMS: &[&dyn $crate::params::DeclaredParam] = &[$($(&$param),*)?];
+
+        static METADATA: $crate::metadata::Metadata = $crate::metadata::Metadata {
+            title: $crate::solution!(@optional $($title)?),
+            tags: &[$($($crate::metadata::Tag::$tag),*)?],
+            notes: $crate::solution!(@optional $($notes)?),
+        };
+
+        pub fn solutions(year: i32, day: i32) -> Vec<$crate::problems::Solution> {
+            #[allow(unused_mut)]
+            let mut solutions = vec![$($crate::problems::Solution {
+                year,
+                day,
+                part: $crate::solution!(@number $part),
+                variant: $crate::problems::DEFAULT_VARIANT,
+                solution: $crate::solution!(@solver $solver, $part),
+                expected: $crate::solution!(@expected $($expected)?),
+                params: PARAMS,
+                metadata: &METADATA,
+            }),+];
+            $($(
+                $crate::solution!(@variants solutions, year, day, $solver, $variant_part, $($variant = $function),+);
+            )*)?
+            return solutions;
+        }
+    };
+}
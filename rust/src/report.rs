@@ -0,0 +1,302 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+
+use crate::answer::Answer;
+use crate::output::{xml_escape, Record, Status};
+use crate::timing::prettify_duration;
+
+/// Markers delimiting the section of a README replaced by the report
+pub const README_START: &str = "<!-- aoc-report:start -->";
+pub const README_END: &str = "<!-- aoc-report:end -->";
+
+const MASK: &str = "••••••";
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown tables, one per year
+    Markdown,
+    /// A self-contained HTML page
+    Html,
+}
+
+struct ReportRow {
+    year: i32,
+    day: i32,
+    title: Option<&'static str>,
+    /// Part, followed by the variant and the example when there are any
+    part: String,
+    answer: Option<Answer>,
+    error: Option<String>,
+    status: Status,
+    time: Option<Duration>,
+    parse: Option<Duration>,
+    /// Whether the parse time is not already counted in the total by an earlier part
+    counts_parse: bool,
+}
+
+impl ReportRow {
+    fn total(&self) -> Duration {
+        let mut total = self.time.unwrap_or_default();
+        if self.counts_parse {
+            total += self.parse.unwrap_or_default();
+        }
+        return total;
+    }
+}
+
+/// Results of a run, rendered as tables of answers and timings grouped by year
+pub struct Report {
+    rows: Vec<ReportRow>,
+    mask_answers: bool,
+    counted_parses: HashSet<(i32, i32, Option<String>)>,
+}
+
+fn duration_cell(duration: Option<Duration>) -> String {
+    return match duration {
+        Some(duration) => prettify_duration(duration),
+        None => String::from("-"),
+    };
+}
+
+impl Report {
+    pub fn new(mask_answers: bool) -> Report {
+        return Report {
+            rows: Vec::new(),
+            mask_answers,
+            counted_parses: HashSet::new(),
+        };
+    }
+
+    pub fn add(&mut self, record: &Record) {
+        let mut part = record.part.to_string();
+        if let Some(variant) = &record.variant {
+            part.push_str(&format!(" ({})", variant));
+        }
+        if let Some(example) = &record.example {
+            part.push_str(&format!(" [{}]", example));
+        }
+
+        let counts_parse = record.parse.is_some()
+            && self
+                .counted_parses
+                .insert((record.year, record.day, record.example.clone()));
+
+        self.rows.push(ReportRow {
+            year: record.year,
+            day: record.day,
+            title: record.title,
+            part,
+            answer: record.answer.clone(),
+            error: record.error.clone(),
+            status: record.status,
+            time: record.time.map(|time| time.wall),
+            parse: record.parse.map(|parse| parse.wall),
+            counts_parse,
+        });
+    }
+
+    fn years(&self) -> Vec<(i32, Vec<&ReportRow>)> {
+        let mut years: Vec<(i32, Vec<&ReportRow>)> = Vec::new();
+        for row in self.rows.iter() {
+            match years.last_mut() {
+                Some((year, rows)) if *year == row.year => rows.push(row),
+                _ => years.push((row.year, vec![row])),
+            }
+        }
+        return years;
+    }
+
+    /// Text of the answer cell, without escaping: masked answers only tell whether there is one
+    fn answer_text(&self, row: &ReportRow) -> String {
+        return match (&row.answer, &row.error) {
+            (Some(_), _) if self.mask_answers => String::from(MASK),
+            (Some(answer), _) => answer.to_string(),
+            (None, Some(error)) => error.clone(),
+            (None, None) => String::new(),
+        };
+    }
+
+    fn total(rows: &[&ReportRow]) -> Duration {
+        return rows.iter().map(|row| row.total()).sum();
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut markdown = String::new();
+        let years = self.years();
+        for (year, rows) in years.iter() {
+            writeln!(markdown, "### {}\n", year).unwrap();
+            writeln!(
+                markdown,
+                "| Day | Puzzle | Part | Answer | | Time | Parse |"
+            )
+            .unwrap();
+            writeln!(markdown, "| ---: | --- | --- | --- | :---: | ---: | ---: |").unwrap();
+            for row in rows.iter() {
+                // grids keep their shape as inline HTML, the only way to break lines in a table cell
+                let answer = match &row.answer {
+                    Some(answer) if answer.is_multiline() && !self.mask_answers => format!(
+                        "<code>{}</code>",
+                        answer
+                            .to_string()
+                            .lines()
+                            .map(|line| xml_escape(line).replace(' ', "&nbsp;"))
+                            .collect::<Vec<String>>()
+                            .join("<br>")
+                    ),
+                    _ => match self.answer_text(row) {
+                        text if text.is_empty() => text,
+                        text => format!("`{}`", text.replace('`', "'")),
+                    },
+                };
+                writeln!(
+                    markdown,
+                    "| {} | {} | {} | {} | {} | {} | {} |",
+                    row.day,
+                    row.title.unwrap_or_default().replace('|', "\\|"),
+                    row.part,
+                    answer.replace('|', "\\|"),
+                    row.status.indicator(),
+                    duration_cell(row.time),
+                    duration_cell(row.parse)
+                )
+                .unwrap();
+            }
+            writeln!(
+                markdown,
+                "\n**Total:** {}\n",
+                prettify_duration(Report::total(rows))
+            )
+            .unwrap();
+        }
+
+        if years.len() > 1 {
+            let all = self.rows.iter().collect::<Vec<&ReportRow>>();
+            writeln!(
+                markdown,
+                "**Total across years:** {}\n",
+                prettify_duration(Report::total(&all))
+            )
+            .unwrap();
+        }
+
+        return markdown.trim_end().to_string() + "\n";
+    }
+
+    pub fn html(&self) -> String {
+        let mut html = String::from(concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n",
+            "<title>Advent of Code</title>\n<style>\n",
+            "body { font-family: sans-serif; margin: 2em; }\n",
+            "table { border-collapse: collapse; margin-bottom: 1em; }\n",
+            "th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }\n",
+            "td.number { text-align: right; }\n",
+            "pre, code { margin: 0; font-family: monospace; }\n",
+            "</style>\n</head>\n<body>\n<h1>Advent of Code</h1>\n",
+        ));
+
+        let years = self.years();
+        for (year, rows) in years.iter() {
+            writeln!(html, "<h2>{}</h2>\n<table>", year).unwrap();
+            writeln!(html, "<tr><th>Day</th><th>Puzzle</th><th>Part</th><th>Answer</th><th></th><th>Time</th><th>Parse</th></tr>").unwrap();
+            for row in rows.iter() {
+                let answer = match &row.answer {
+                    Some(answer) if answer.is_multiline() && !self.mask_answers => {
+                        format!("<pre>{}</pre>", xml_escape(&answer.to_string()))
+                    }
+                    _ => format!("<code>{}</code>", xml_escape(&self.answer_text(row))),
+                };
+                writeln!(
+                    html,
+                    "<tr><td class=\"number\">{}</td><td>{}</td><td>{}</td><td>{}</td><td title=\"{}\">{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td></tr>",
+                    row.day,
+                    xml_escape(row.title.unwrap_or_default()),
+                    xml_escape(&row.part),
+                    answer,
+                    row.status.name(),
+                    row.status.indicator(),
+                    duration_cell(row.time),
+                    duration_cell(row.parse)
+                )
+                .unwrap();
+            }
+            writeln!(
+                html,
+                "</table>\n<p><strong>Total:</strong> {}</p>",
+                prettify_duration(Report::total(rows))
+            )
+            .unwrap();
+        }
+
+        if years.len() > 1 {
+            let all = self.rows.iter().collect::<Vec<&ReportRow>>();
+            writeln!(
+                html,
+                "<p><strong>Total across years:</strong> {}</p>",
+                prettify_duration(Report::total(&all))
+            )
+            .unwrap();
+        }
+
+        html.push_str("</body>\n</html>\n");
+        return html;
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        return match format {
+            ReportFormat::Markdown => self.markdown(),
+            ReportFormat::Html => self.html(),
+        };
+    }
+}
+
+fn read_readme(path: &Path) -> Result<String> {
+    return fs::read_to_string(path)
+        .with_context(|| format!("could not read README {}", path.display()));
+}
+
+/// Bounds of the text between the report markers
+fn marked_section(readme: &str, path: &Path) -> Result<(usize, usize)> {
+    let start = match readme.find(README_START) {
+        Some(start) => start + README_START.len(),
+        None => bail!(
+            "{} has no {} marker, add it and {} where the report should go",
+            path.display(),
+            README_START,
+            README_END
+        ),
+    };
+    let end = match readme[start..].find(README_END) {
+        Some(end) => start + end,
+        None => bail!(
+            "{} has no {} marker after {}",
+            path.display(),
+            README_END,
+            README_START
+        ),
+    };
+
+    return Ok((start, end));
+}
+
+/// Checks that a README can be updated, before spending time on running the solutions
+pub fn check_readme(path: &Path) -> Result<()> {
+    return marked_section(&read_readme(path)?, path).map(|_| ());
+}
+
+/// Replaces the section between the report markers of a README with the given content
+pub fn update_readme(path: &Path, content: &str) -> Result<()> {
+    let readme = read_readme(path)?;
+    let (start, end) = marked_section(&readme, path)?;
+
+    let updated = format!("{}\n{}{}", &readme[..start], content, &readme[end..]);
+    fs::write(path, updated)
+        .with_context(|| format!("could not write README {}", path.display()))?;
+
+    return Ok(());
+}
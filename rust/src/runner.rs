@@ -0,0 +1,483 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, OnceLock};
+use std::thread;
+use std::time::Instant;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::answer::Answer;
+use crate::answers::ExpectedAnswers;
+use crate::baseline::{self, Baseline, BaselineEntry};
+use crate::bench::{bench, BenchOptions};
+use crate::day::{Parsed, Solver};
+use crate::environment::Environment;
+use crate::examples::Examples;
+use crate::input::InputResolver;
+use crate::output::{reporter, OutputFormat, Record, Summary};
+use crate::panics::{self, call_guarded, SolutionPanic};
+use crate::params::{self, Params};
+use crate::problems::{Solution, SOLUTIONS};
+use crate::sandbox::{self, run_isolated};
+use crate::timing::{measure, Measurement};
+use crate::variants::VariantComparison;
+
+pub use crate::sandbox::{SandboxOptions, CHILD_ARGUMENT};
+
+/// Outcome of parsing one input, shared by the parts of a day so that it is parsed only once
+type SharedParse = Arc<OnceLock<(Result<Parsed>, Measurement)>>;
+
+/// Parse outcomes of every input in a run, keyed by year, day and example
+#[derive(Default)]
+pub struct ParseCache {
+    parses: HashMap<(i32, i32, Option<String>), SharedParse>,
+}
+
+impl ParseCache {
+    fn shared(&mut self, solution: &Solution, example: Option<&str>) -> SharedParse {
+        return self
+            .parses
+            .entry((solution.year, solution.day, example.map(String::from)))
+            .or_default()
+            .clone();
+    }
+}
+
+pub struct Task<'a> {
+    solution: &'a Solution,
+    example: Option<String>,
+    input: String,
+    expected: Option<Answer>,
+    params: Params,
+    parse: SharedParse,
+}
+
+pub fn collect_tasks<'a>(
+    solution: &'a Solution,
+    input_resolver: &InputResolver,
+    expected_answers: &ExpectedAnswers,
+    params: &Params,
+    use_examples: bool,
+    parse_cache: &mut ParseCache,
+) -> Result<Vec<Task<'a>>, String> {
+    if !use_examples {
+        let input = input_resolver
+            .read(solution.year, solution.day)
+            .map_err(|e| format!("could not read puzzle input: {}", e))?;
+
+        return Ok(vec![Task {
+            solution,
+            example: None,
+            input,
+            expected: expected_answers.for_input(solution, input_resolver),
+            params: params.clone(),
+            parse: parse_cache.shared(solution, None),
+        }]);
+    }
+
+    let examples = Examples::load(input_resolver, solution.year, solution.day)
+        .and_then(|examples| match examples {
+            Some(examples) => examples.for_part(solution.part),
+            None => Ok(Vec::new()),
+        })
+        .map_err(|e| format!("could not load examples: {:#}", e))?;
+
+    let mut tasks: Vec<Task> = Vec::new();
+    for example in examples {
+        // parameters given on the command line take precedence over those of the example
+        let mut example_params = example.params;
+        example_params.extend(params.clone());
+        params::check(&[solution], &example_params)
+            .map_err(|e| format!("invalid parameters for example {}: {}", example.name, e))?;
+
+        tasks.push(Task {
+            solution,
+            parse: parse_cache.shared(solution, Some(&example.name)),
+            example: Some(example.name),
+            input: example.input,
+            expected: Some(example.expected),
+            params: example_params,
+        });
+    }
+
+    return Ok(tasks);
+}
+
+/// A unit of work in a run: either a task still to execute or a record already known to have failed
+pub enum Job<'a> {
+    Pending(Task<'a>),
+    Failed(Box<Record>),
+}
+
+pub struct ExecutionOptions {
+    pub bench: Option<BenchOptions>,
+    pub sandbox: Option<SandboxOptions>,
+}
+
+/// Parses an input, timing the parse on its own and reporting panics like those of a solution
+pub fn parse_guarded(
+    parse: fn(&str) -> Result<Parsed>,
+    input: &str,
+) -> (Result<Parsed>, Measurement) {
+    return measure(|| call_guarded(|| parse(input).context("could not parse input")));
+}
+
+/// Copies the error of a shared parse, keeping panics recognizable
+fn parse_error(error: &anyhow::Error) -> anyhow::Error {
+    return match error.downcast_ref::<SolutionPanic>() {
+        Some(panic) => panic.clone().into(),
+        None => anyhow!("{:#}", error),
+    };
+}
+
+fn execute(task: &Task, options: &ExecutionOptions) -> Record {
+    if let (None, Some(sandbox_options)) = (&options.bench, &options.sandbox) {
+        let (result, time_taken, parse_time) =
+            run_isolated(task.solution, &task.input, &task.params, sandbox_options);
+        let record = Record::new(
+            task.solution,
+            task.expected.clone(),
+            result,
+            time_taken,
+            None,
+        );
+        return finish_record(task, record, parse_time);
+    }
+
+    let (result, time_taken, report, parse_time) = match task.solution.solution {
+        Solver::Raw(solve) => {
+            let (result, time_taken, report) = match &options.bench {
+                Some(bench_options) => {
+                    let (result, report) = bench(|| task.input.clone(), solve, bench_options);
+                    (result, report.typical(), Some(report))
+                }
+                None => {
+                    let input = task.input.clone();
+                    let (result, time_taken) = measure(|| call_guarded(|| solve(input)));
+                    (result, time_taken, None)
+                }
+            };
+            (result, time_taken, report, None)
+        }
+        Solver::Parsed { parse, solve } => {
+            let (parsed, parse_time) = task.parse.get_or_init(|| parse_guarded(parse, &task.input));
+            let (result, time_taken, report) = match (parsed, &options.bench) {
+                (Err(e), _) => (Err(parse_error(e)), Measurement::default(), None),
+                (Ok(parsed), Some(bench_options)) => {
+                    let (result, report) = bench(|| (), |_| solve(parsed), bench_options);
+                    (result, report.typical(), Some(report))
+                }
+                (Ok(parsed), None) => {
+                    let (result, time_taken) = measure(|| call_guarded(|| solve(parsed)));
+                    (result, time_taken, None)
+                }
+            };
+            (result, time_taken, report, Some(*parse_time))
+        }
+    };
+
+    let record = Record::new(
+        task.solution,
+        task.expected.clone(),
+        result,
+        time_taken,
+        report,
+    );
+    return finish_record(task, record, parse_time);
+}
+
+fn finish_record(task: &Task, record: Record, parse_time: Option<Measurement>) -> Record {
+    let record = match parse_time {
+        Some(parse_time) => record.with_parse(parse_time),
+        None => record,
+    };
+    return match &task.example {
+        Some(example) => record.with_example(example),
+        None => record,
+    };
+}
+
+/// Executes the jobs on up to `workers` threads, handing records to `on_record` in the jobs' order
+pub fn run(
+    jobs: Vec<Job>,
+    workers: usize,
+    options: &ExecutionOptions,
+    mut on_record: impl FnMut(&Record),
+) -> Summary {
+    let started = Instant::now();
+    let count = jobs.len();
+    let next_job = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel::<(usize, Record)>();
+
+    let mut total = Measurement::default();
+    let mut counted_parses: HashSet<(i32, i32, Option<String>)> = HashSet::new();
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, count.max(1)) {
+            let sender = sender.clone();
+            let jobs = &jobs;
+            let next_job = &next_job;
+            scope.spawn(move || loop {
+                let idx = next_job.fetch_add(1, Ordering::Relaxed);
+                let record = match jobs.get(idx) {
+                    Some(Job::Pending(task)) => {
+                        params::with_params(&task.params, || execute(task, options))
+                    }
+                    Some(Job::Failed(_)) => continue,
+                    None => break,
+                };
+                if sender.send((idx, record)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut completed: Vec<Option<Record>> = (0..count).map(|_| None).collect();
+        let mut next_to_report: usize = 0;
+        let mut flush = |completed: &mut Vec<Option<Record>>, next_to_report: &mut usize| {
+            while *next_to_report < count {
+                let record = match &jobs[*next_to_report] {
+                    Job::Failed(record) => record,
+                    Job::Pending(_) => match &completed[*next_to_report] {
+                        Some(record) => record,
+                        None => break,
+                    },
+                };
+                if let Some(time) = record.time {
+                    total += time;
+                }
+                if let Some(parse) = record.parse {
+                    if counted_parses.insert((record.year, record.day, record.example.clone())) {
+                        total += parse;
+                    }
+                }
+                on_record(record);
+                completed[*next_to_report] = None;
+                *next_to_report += 1;
+            }
+        };
+
+        flush(&mut completed, &mut next_to_report);
+        for (idx, record) in receiver {
+            completed[idx] = Some(record);
+            flush(&mut completed, &mut next_to_report);
+        }
+    });
+
+    return Summary {
+        count,
+        total,
+        elapsed: started.elapsed(),
+    };
+}
+
+/// Collects the jobs running the solutions on their inputs, or on their examples when
+/// `use_examples`, sharing the parse of an input between the parts of a day
+pub fn collect_jobs<'a>(
+    solutions: &[&'a Solution],
+    input_resolver: &InputResolver,
+    expected_answers: &ExpectedAnswers,
+    params: &Params,
+    use_examples: bool,
+) -> Vec<Job<'a>> {
+    let mut parse_cache = ParseCache::default();
+    return solutions
+        .iter()
+        .flat_map(|solution| {
+            match collect_tasks(
+                solution,
+                input_resolver,
+                expected_answers,
+                params,
+                use_examples,
+                &mut parse_cache,
+            ) {
+                Ok(tasks) => tasks.into_iter().map(Job::Pending).collect(),
+                Err(e) => vec![Job::Failed(Box::new(Record::not_run(
+                    solution,
+                    expected_answers.for_input(solution, input_resolver),
+                    e,
+                )))],
+            }
+        })
+        .collect();
+}
+
+/// How a selection of solutions is run, beyond how each of them is executed
+pub struct RunSettings {
+    pub execution: ExecutionOptions,
+    /// Number of solutions executed in parallel
+    pub jobs: usize,
+    pub output: OutputFormat,
+    /// Parameters overriding those of the selected solutions
+    pub params: Params,
+    /// Run the solutions against their registered examples instead of the real inputs
+    pub use_examples: bool,
+    /// Write the answers of solutions without an expected answer to the answers file
+    pub record: bool,
+    /// Check that the variants of each part agree and compare their timings
+    pub compare_variants: bool,
+    /// Name of a baseline to save the timings of the run as
+    pub save_baseline: Option<String>,
+    /// Name of a baseline to compare the timings against, with the slowdown, in percent, above
+    /// which a solution is reported as a regression
+    pub compare: Option<(String, f64)>,
+}
+
+/// What a run of a selection kept besides the records it reported
+pub struct RunOutcome {
+    /// Whether a solution crashed, regressed or disagreed with another variant
+    pub failed: bool,
+    /// File the timings were saved to as a baseline
+    pub saved_baseline: Option<PathBuf>,
+    /// Number of answers recorded, with the answers file they were written to
+    pub recorded: Option<(usize, PathBuf)>,
+}
+
+/// Checks that the solutions can be run with the settings, before running any of them
+pub(crate) fn check_run(
+    solutions: &[&Solution],
+    environment: &Environment,
+    settings: &RunSettings,
+) -> Result<()> {
+    if environment.input_resolver.is_explicit()
+        && solutions
+            .iter()
+            .any(|solution| (solution.year, solution.day) != (solutions[0].year, solutions[0].day))
+    {
+        bail!("an explicit input can only be used for a single day");
+    }
+
+    return params::check(solutions, &settings.params);
+}
+
+/// Keeps the timings shown by the status command, which belong to the default inputs only, when
+/// there is a data directory to keep them in
+pub(crate) fn update_last_timings(timings: Baseline, environment: &Environment, params: &Params) {
+    let data_dir = match environment.data_dir() {
+        Some(data_dir) => data_dir,
+        None => return,
+    };
+    if !environment.input_resolver.is_default() || !params.is_empty() || timings.entries.is_empty()
+    {
+        return;
+    }
+
+    if let Err(e) = timings.save_last(data_dir) {
+        eprintln!("could not update the last known timings: {:#}", e);
+    }
+}
+
+/// Runs the solutions, handing every record to `observe` and reporting it unless `quiet`, then
+/// compares, saves and records what the settings ask for
+pub fn run_selection(
+    solutions: &[&Solution],
+    environment: &mut Environment,
+    settings: &RunSettings,
+    quiet: bool,
+    mut observe: impl FnMut(&Record),
+) -> Result<RunOutcome> {
+    check_run(solutions, environment, settings)?;
+    if let Some(name) = &settings.save_baseline {
+        baseline::baseline_path(environment.require_data_dir()?, name)?;
+    }
+    let compared_baseline = match &settings.compare {
+        Some((name, threshold)) => Some((
+            Baseline::load(environment.require_data_dir()?, name)?,
+            *threshold,
+        )),
+        None => None,
+    };
+
+    let jobs = collect_jobs(
+        solutions,
+        &environment.input_resolver,
+        &environment.expected_answers,
+        &settings.params,
+        settings.use_examples,
+    );
+    if settings.use_examples && jobs.is_empty() {
+        bail!("no examples registered for the given criteria.");
+    }
+
+    panics::install_hook();
+
+    let mut reporter = reporter(settings.output);
+    let mut crashed = false;
+    let mut timings = Baseline::default();
+    let mut recorded: usize = 0;
+    let mut variant_comparison = VariantComparison::default();
+    let summary = run(jobs, settings.jobs, &settings.execution, |record| {
+        crashed |= record.status.crashed();
+        timings.entries.extend(BaselineEntry::from_record(record));
+        if settings.record && environment.expected_answers.record(record) {
+            recorded += 1;
+        }
+        if settings.compare_variants {
+            variant_comparison.add(record);
+        }
+        observe(record);
+        if !quiet {
+            reporter.report(record);
+        }
+    });
+    if !quiet {
+        reporter.finish(&summary);
+    }
+
+    if !variant_comparison.is_empty() {
+        match settings.output {
+            OutputFormat::Human => println!("\n{}", variant_comparison),
+            _ => eprint!("{}", variant_comparison),
+        }
+    }
+
+    let mut regressed = false;
+    if let Some((compared_baseline, threshold)) = &compared_baseline {
+        let comparisons = baseline::compare(compared_baseline, &timings, *threshold);
+        let lines = comparisons.iter().map(|comparison| comparison.to_string());
+        match settings.output {
+            OutputFormat::Human => {
+                println!();
+                lines.for_each(|line| println!("{}", line));
+            }
+            _ => lines.for_each(|line| eprintln!("{}", line)),
+        }
+        regressed = comparisons.iter().any(|comparison| comparison.regressed);
+    }
+
+    update_last_timings(timings.clone(), environment, &settings.params);
+
+    let saved_baseline = match &settings.save_baseline {
+        Some(name) => Some(timings.save(environment.require_data_dir()?, name)?),
+        None => None,
+    };
+    let recorded = match recorded {
+        0 => None,
+        recorded => Some((recorded, environment.expected_answers.save()?)),
+    };
+
+    return Ok(RunOutcome {
+        failed: crashed || regressed || !variant_comparison.agrees(),
+        saved_baseline,
+        recorded,
+    });
+}
+
+/// Entry point of the binary re-executed by the runner, with [`CHILD_ARGUMENT`] set to `argument`,
+/// to run a solution in isolation: runs the solution it names on stdin with the given parameters
+pub fn run_isolated_child(argument: &str, params: &Params) -> ! {
+    panics::install_hook();
+    match SOLUTIONS
+        .iter()
+        .find(|solution| sandbox::child_argument(solution) == argument)
+    {
+        Some(solution) => params::with_params(params, || sandbox::run_child(solution)),
+        None => {
+            eprintln!("no solution matches {}", argument);
+            process::exit(1);
+        }
+    }
+}
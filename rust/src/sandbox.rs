@@ -0,0 +1,290 @@
+use std::io::{self, Read, Write};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::allocations::AllocationStats;
+use crate::answer::Answer;
+use crate::day::Solver;
+use crate::panics::{call_guarded, SolutionPanic};
+use crate::params::Params;
+use crate::problems::Solution;
+use crate::runner::parse_guarded;
+use crate::timing::{measure, Measurement};
+
+/// Hidden argument used by the runner to re-execute itself as an isolated child
+pub const CHILD_ARGUMENT: &str = "isolated-child";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+/// Exit code of a child that could not allocate memory without aborting, such as while reading its input
+const OUT_OF_MEMORY_EXIT_CODE: i32 = 3;
+/// Message printed by the standard library when an allocation fails and the process aborts
+const ALLOCATION_FAILURE_MESSAGE: &str = "memory allocation of";
+
+pub struct SandboxOptions {
+    pub timeout: Duration,
+    pub memory_limit: Option<u64>,
+    pub cpu_limit: Option<u64>,
+}
+
+#[derive(Error, Debug)]
+pub enum SandboxError {
+    #[error("solution timed out after {0:?}")]
+    Timeout(Duration),
+    #[error("solution exceeded the cpu time limit of {0}s")]
+    CpuLimit(u64),
+    #[error("solution ran out of memory")]
+    OutOfMemory,
+    #[error("solution was killed by signal {0}")]
+    Signal(i32),
+}
+
+#[derive(Serialize, Deserialize)]
+struct ChildReport {
+    answer: Option<Answer>,
+    error: Option<String>,
+    panic: Option<(String, String)>,
+    wall_ns: u64,
+    cpu_ns: u64,
+    parse_wall_ns: Option<u64>,
+    parse_cpu_ns: Option<u64>,
+    allocations: Option<AllocationStats>,
+    parse_allocations: Option<AllocationStats>,
+}
+
+pub fn child_argument(solution: &Solution) -> String {
+    let argument = format!("{}/{}/{}", solution.year, solution.day, solution.part);
+    return match solution.is_default_variant() {
+        true => argument,
+        false => format!("{}/{}", argument, solution.variant),
+    };
+}
+
+/// Entry point of the child process: runs one solution on stdin and reports the outcome on stdout
+pub fn run_child(solution: &Solution) -> ! {
+    let mut input = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut input) {
+        eprintln!("could not read input from parent: {}", e);
+        process::exit(match e.kind() {
+            io::ErrorKind::OutOfMemory => OUT_OF_MEMORY_EXIT_CODE,
+            _ => 1,
+        });
+    }
+
+    let (result, time_taken, parse_time) = match solution.solution {
+        Solver::Raw(solve) => {
+            let (result, time_taken) = measure(|| call_guarded(|| solve(input)));
+            (result, time_taken, None)
+        }
+        Solver::Parsed { parse, solve } => {
+            let (parsed, parse_time) = parse_guarded(parse, &input);
+            let (result, time_taken) = match parsed {
+                Ok(parsed) => measure(|| call_guarded(|| solve(&parsed))),
+                Err(e) => (Err(e), Measurement::default()),
+            };
+            (result, time_taken, Some(parse_time))
+        }
+    };
+    let mut report = ChildReport {
+        answer: None,
+        error: None,
+        panic: None,
+        wall_ns: time_taken.wall.as_nanos() as u64,
+        cpu_ns: time_taken.cpu.as_nanos() as u64,
+        parse_wall_ns: parse_time.map(|parse_time| parse_time.wall.as_nanos() as u64),
+        parse_cpu_ns: parse_time.map(|parse_time| parse_time.cpu.as_nanos() as u64),
+        allocations: time_taken.allocations,
+        parse_allocations: parse_time.and_then(|parse_time| parse_time.allocations),
+    };
+    match result {
+        Ok(answer) => report.answer = Some(answer),
+        Err(e) => match e.downcast::<SolutionPanic>() {
+            Ok(panic) => report.panic = Some((panic.message, panic.location)),
+            Err(e) => report.error = Some(e.to_string()),
+        },
+    }
+
+    match serde_json::to_string(&report) {
+        Ok(line) => println!("{}", line),
+        Err(e) => {
+            eprintln!("could not serialize report: {}", e);
+            process::exit(1);
+        }
+    }
+    process::exit(0);
+}
+
+fn limit_resources(memory_limit: Option<u64>, cpu_limit: Option<u64>) -> io::Result<()> {
+    let limits = [
+        (libc::RLIMIT_AS, memory_limit, memory_limit),
+        (
+            libc::RLIMIT_CPU,
+            cpu_limit,
+            cpu_limit.map(|limit| limit + 1),
+        ),
+    ];
+
+    for (resource, soft, hard) in limits {
+        if let (Some(soft), Some(hard)) = (soft, hard) {
+            let limit = libc::rlimit {
+                rlim_cur: soft as libc::rlim_t,
+                rlim_max: hard as libc::rlim_t,
+            };
+            // SAFETY: setrlimit only reads the given struct and is async-signal-safe
+            if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<Option<ExitStatus>> {
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if started.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Runs a solution in a child process, subject to the sandbox's time and resource limits,
+/// returning its outcome along with the time taken to solve and, if done separately, to parse
+pub fn run_isolated(
+    solution: &Solution,
+    input: &str,
+    params: &Params,
+    options: &SandboxOptions,
+) -> (Result<Answer>, Measurement, Option<Measurement>) {
+    let started = Instant::now();
+    let outcome = spawn_and_collect(solution, input, params, options);
+    let elapsed = Measurement {
+        wall: started.elapsed(),
+        cpu: Duration::ZERO,
+        allocations: None,
+    };
+
+    return match outcome {
+        Ok(report) => {
+            let time_taken = Measurement {
+                wall: Duration::from_nanos(report.wall_ns),
+                cpu: Duration::from_nanos(report.cpu_ns),
+                allocations: report.allocations,
+            };
+            let parse_time = match (report.parse_wall_ns, report.parse_cpu_ns) {
+                (Some(wall_ns), Some(cpu_ns)) => Some(Measurement {
+                    wall: Duration::from_nanos(wall_ns),
+                    cpu: Duration::from_nanos(cpu_ns),
+                    allocations: report.parse_allocations,
+                }),
+                _ => None,
+            };
+            let result = match (report.answer, report.error, report.panic) {
+                (Some(answer), _, _) => Ok(answer),
+                (_, _, Some((message, location))) => {
+                    Err(SolutionPanic { message, location }.into())
+                }
+                (_, Some(error), _) => Err(anyhow!(error)),
+                (None, None, None) => Err(anyhow!("child reported neither an answer nor an error")),
+            };
+            (result, time_taken, parse_time)
+        }
+        Err(e) => (Err(e), elapsed, None),
+    };
+}
+
+fn spawn_and_collect(
+    solution: &Solution,
+    input: &str,
+    params: &Params,
+    options: &SandboxOptions,
+) -> Result<ChildReport> {
+    let executable = env::current_exe().context("could not locate the runner executable")?;
+    let memory_limit = options.memory_limit;
+    let cpu_limit = options.cpu_limit;
+
+    let mut command = Command::new(executable);
+    command
+        .arg(format!("--{}", CHILD_ARGUMENT))
+        .arg(child_argument(solution))
+        .args(
+            params
+                .iter()
+                .flat_map(|(name, value)| ["--param".into(), format!("{}={}", name, value)]),
+        )
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    // SAFETY: the closure only calls setrlimit, which is async-signal-safe
+    unsafe {
+        command.pre_exec(move || limit_resources(memory_limit, cpu_limit));
+    }
+
+    let mut child = command.spawn().context("could not spawn isolated child")?;
+
+    let mut stdin = child.stdin.take().context("child stdin unavailable")?;
+    let owned_input = input.to_string();
+    let writer = thread::spawn(move || stdin.write_all(owned_input.as_bytes()));
+
+    let mut stdout = child.stdout.take().context("child stdout unavailable")?;
+    let stdout_reader = thread::spawn(move || {
+        let mut output = String::new();
+        _ = stdout.read_to_string(&mut output);
+        output
+    });
+    let mut stderr = child.stderr.take().context("child stderr unavailable")?;
+    let stderr_reader = thread::spawn(move || {
+        let mut output = String::new();
+        _ = stderr.read_to_string(&mut output);
+        output
+    });
+
+    let status = wait_with_timeout(&mut child, options.timeout)?;
+    _ = writer.join();
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    let status = match status {
+        Some(status) => status,
+        None => return Err(SandboxError::Timeout(options.timeout).into()),
+    };
+
+    if stderr.contains(ALLOCATION_FAILURE_MESSAGE) || status.code() == Some(OUT_OF_MEMORY_EXIT_CODE)
+    {
+        return Err(SandboxError::OutOfMemory.into());
+    }
+    if let Some(signal) = status.signal() {
+        // under an address space limit, a stack that cannot grow faults and the kernel's out of
+        // memory killer sends SIGKILL, neither of which tells the two apart from other causes
+        return Err(match (signal, cpu_limit, memory_limit) {
+            (libc::SIGXCPU, Some(cpu_limit), _) => SandboxError::CpuLimit(cpu_limit),
+            (libc::SIGSEGV | libc::SIGKILL, _, Some(_)) => SandboxError::OutOfMemory,
+            (signal, _, _) => SandboxError::Signal(signal),
+        }
+        .into());
+    }
+    if !status.success() {
+        return Err(anyhow!("child exited with {}: {}", status, stderr.trim()));
+    }
+
+    let report_line = stdout
+        .lines()
+        .rev()
+        .find(|line| !line.is_empty())
+        .context("child produced no report")?;
+    return serde_json::from_str::<ChildReport>(report_line)
+        .context("could not parse child report");
+}
@@ -0,0 +1,217 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+use aoc::selection::last_day;
+
+const TEMPLATE: &str = include_str!("../templates/problem.rs.template");
+/// Sources the registry is generated from, so that a new day is picked up by the next build
+const SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+/// Manifest declaring the feature of each year
+const MANIFEST: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+
+const FIRST_YEAR: i32 = 2015;
+
+/// Source file of a day
+pub fn problem_path(year: i32, day: i32) -> PathBuf {
+    return PathBuf::from(SOURCE_DIR)
+        .join(format!("year_{}", year))
+        .join(format!("problem_{}.rs", day));
+}
+
+pub struct Scaffold {
+    pub path: PathBuf,
+    /// Feature of the year, when it had to be added to the manifest for the day to be compiled
+    pub added_feature: Option<String>,
+}
+
+fn is_feature_line(line: &str) -> bool {
+    return line
+        .split_once('=')
+        .is_some_and(|(name, _)| name.trim().starts_with("year-"));
+}
+
+/// The manifest with the feature of a year declared and enabled by default, or `None` when it
+/// already is declared
+fn with_year_feature(manifest: &str, feature: &str) -> Result<Option<String>> {
+    let mut lines = manifest.lines().map(String::from).collect::<Vec<String>>();
+    let features_start = match lines.iter().position(|line| line.trim() == "[features]") {
+        Some(start) => start + 1,
+        None => bail!("the manifest has no [features] section"),
+    };
+    let features_end = lines[features_start..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |end| features_start + end);
+    let section = features_start..features_end;
+
+    if lines[section.clone()].iter().any(|line| {
+        line.split_once('=')
+            .is_some_and(|(name, _)| name.trim() == feature)
+    }) {
+        return Ok(None);
+    }
+
+    let default = match lines[section.clone()]
+        .iter()
+        .position(|line| line.trim_start().starts_with("default"))
+    {
+        Some(default) => features_start + default,
+        None => bail!("the manifest has no default features"),
+    };
+    let enabled = match lines[default]
+        .split_once('[')
+        .and_then(|(_, rest)| rest.rsplit_once(']'))
+    {
+        Some((enabled, _)) => enabled,
+        None => bail!("the default features of the manifest are not on a single line"),
+    };
+    let mut enabled = enabled
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<String>>();
+    let position = enabled
+        .iter()
+        .rposition(|name| name.starts_with("\"year-"))
+        .map_or(0, |last| last + 1);
+    enabled.insert(position, format!("{:?}", feature));
+    lines[default] = format!("default = [{}]", enabled.join(", "));
+
+    // the feature goes after the other years, or else right after the default features
+    let declaration = lines[section.clone()]
+        .iter()
+        .rposition(|line| is_feature_line(line))
+        .map_or(default + 1, |last| features_start + last + 1);
+    lines.insert(declaration, format!("{} = []", feature));
+
+    return Ok(Some(lines.join("\n") + "\n"));
+}
+
+/// Declares the feature of a year in the manifest, returning it if it was missing
+fn add_year_feature(year: i32) -> Result<Option<String>> {
+    let feature = format!("year-{}", year);
+    let manifest =
+        fs::read_to_string(MANIFEST).with_context(|| format!("could not read {}", MANIFEST))?;
+    let updated = match with_year_feature(&manifest, &feature)
+        .with_context(|| format!("could not add the {} feature to {}", feature, MANIFEST))?
+    {
+        Some(updated) => updated,
+        None => return Ok(None),
+    };
+
+    fs::write(MANIFEST, updated).with_context(|| format!("could not write {}", MANIFEST))?;
+    return Ok(Some(feature));
+}
+
+/// Creates the source file of a day from the template, never overwriting an existing one
+pub fn scaffold(year: i32, day: i32) -> Result<Scaffold> {
+    if year < FIRST_YEAR {
+        bail!("there is no advent of code {}", year);
+    }
+    let last_day = last_day(year) as i32;
+    if !(1..=last_day).contains(&day) {
+        bail!("day must be between 1 and {}, found {}", last_day, day);
+    }
+
+    let path = problem_path(year, day);
+    if let Some(year_dir) = path.parent() {
+        fs::create_dir_all(year_dir)
+            .with_context(|| format!("could not create {}", year_dir.display()))?;
+    }
+
+    let mut file = match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            bail!(
+                "{} already exists, refusing to overwrite it",
+                path.display()
+            )
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("could not create {}", path.display()));
+        }
+    };
+
+    let source = TEMPLATE
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string());
+    file.write_all(source.as_bytes())
+        .with_context(|| format!("could not write {}", path.display()))?;
+
+    let added_feature = add_year_feature(year)?;
+
+    return Ok(Scaffold {
+        path,
+        added_feature,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_MANIFEST: &str = concat!(
+        "[package]\n",
+        "name = \"aoc\"\n",
+        "\n",
+        "[features]\n",
+        "default = [\"year-2020\", \"serve\"]\n",
+        "# One feature per year\n",
+        "year-2020 = [\"regex\"]\n",
+        "# Serves solutions\n",
+        "serve = [\"dep:tiny_http\"]\n",
+        "\n",
+        "[lints.clippy]\n",
+        "needless_return = \"allow\"\n",
+    );
+
+    #[test]
+    fn new_years_are_declared_and_enabled_by_default() {
+        let updated = with_year_feature(SAMPLE_MANIFEST, "year-2024")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated,
+            concat!(
+                "[package]\n",
+                "name = \"aoc\"\n",
+                "\n",
+                "[features]\n",
+                "default = [\"year-2020\", \"year-2024\", \"serve\"]\n",
+                "# One feature per year\n",
+                "year-2020 = [\"regex\"]\n",
+                "year-2024 = []\n",
+                "# Serves solutions\n",
+                "serve = [\"dep:tiny_http\"]\n",
+                "\n",
+                "[lints.clippy]\n",
+                "needless_return = \"allow\"\n",
+            )
+        );
+    }
+
+    #[test]
+    fn declared_years_are_left_alone() {
+        assert!(with_year_feature(SAMPLE_MANIFEST, "year-2020")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn the_first_year_follows_the_default_features() {
+        let manifest = "[features]\ndefault = []\nextra = []\n";
+        assert_eq!(
+            with_year_feature(manifest, "year-2015").unwrap().unwrap(),
+            "[features]\ndefault = [\"year-2015\"]\nyear-2015 = []\nextra = []\n"
+        );
+    }
+
+    #[test]
+    fn manifests_without_default_features_are_rejected() {
+        assert!(with_year_feature("[package]\n", "year-2024").is_err());
+        assert!(with_year_feature("[features]\nextra = []\n", "year-2024").is_err());
+    }
+}
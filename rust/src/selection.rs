@@ -0,0 +1,430 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::metadata::Tag;
+use crate::problems::{self, Solution, SOLUTIONS};
+
+/// Set of years, days or parts written as a comma separated list of numbers and ranges, such as
+/// `1..=5,9`, `1..6` or `20..`
+#[derive(Clone, Debug)]
+pub struct NumberSet {
+    ranges: Vec<RangeInclusive<i32>>,
+}
+
+fn parse_bound(raw: &str) -> Result<i32> {
+    return raw
+        .trim()
+        .parse::<i32>()
+        .with_context(|| format!("invalid number {:?}", raw));
+}
+
+fn parse_range(raw: &str) -> Result<RangeInclusive<i32>> {
+    let (start, end) = match raw.split_once("..") {
+        Some(bounds) => bounds,
+        None => {
+            let number = parse_bound(raw)?;
+            return Ok(number..=number);
+        }
+    };
+
+    let start = match start.trim() {
+        "" => i32::MIN,
+        start => parse_bound(start)?,
+    };
+    let end = match end.trim().strip_prefix('=') {
+        Some(end) => parse_bound(end)?,
+        None if end.trim().is_empty() => i32::MAX,
+        None => parse_bound(end)?
+            .checked_sub(1)
+            .ok_or(anyhow!("empty range {:?}", raw))?,
+    };
+    if start > end {
+        bail!("empty range {:?}", raw);
+    }
+
+    return Ok(start..=end);
+}
+
+impl FromStr for NumberSet {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<NumberSet> {
+        let ranges = Result::from_iter(raw.split(',').map(parse_range))?;
+        return Ok(NumberSet { ranges });
+    }
+}
+
+impl NumberSet {
+    pub fn single(number: i32) -> NumberSet {
+        return NumberSet {
+            ranges: vec![number..=number],
+        };
+    }
+
+    pub fn contains(&self, number: i32) -> bool {
+        return self.ranges.iter().any(|range| range.contains(&number));
+    }
+
+    /// The number in the set, if it holds exactly one
+    pub fn as_single(&self) -> Option<i32> {
+        let first = *self.ranges.first()?.start();
+        return match self.ranges.iter().all(|range| *range == (first..=first)) {
+            true => Some(first),
+            false => None,
+        };
+    }
+}
+
+/// Solutions to leave out of a selection, written as `year`, `year/day` or `year/day/part`
+#[derive(Clone, Debug)]
+pub struct Skip {
+    year: i32,
+    day: Option<i32>,
+    part: Option<i32>,
+}
+
+impl FromStr for Skip {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Skip> {
+        let numbers: Vec<i32> = Result::from_iter(raw.split('/').map(parse_bound))
+            .map_err(|_| anyhow!("expected year, year/day or year/day/part, found {}", raw))?;
+
+        return match numbers[..] {
+            [year] => Ok(Skip {
+                year,
+                day: None,
+                part: None,
+            }),
+            [year, day] => Ok(Skip {
+                year,
+                day: Some(day),
+                part: None,
+            }),
+            [year, day, part] => Ok(Skip {
+                year,
+                day: Some(day),
+                part: Some(part),
+            }),
+            _ => bail!("expected year, year/day or year/day/part, found {}", raw),
+        };
+    }
+}
+
+impl Skip {
+    pub fn matches(&self, solution: &Solution) -> bool {
+        return self.year == solution.year
+            && self.day.is_none_or(|day| day == solution.day)
+            && self.part.is_none_or(|part| part == solution.part);
+    }
+}
+
+/// Criteria choosing solutions from the registry, each of them matching every solution when unset
+#[derive(Default)]
+pub struct Criteria {
+    pub years: Option<NumberSet>,
+    pub days: Option<NumberSet>,
+    pub parts: Option<NumberSet>,
+    /// Only select the highest registered day among the ones matching the other criteria
+    pub latest: bool,
+    /// Techniques of which a day must be tagged with at least one
+    pub tags: Vec<Tag>,
+    pub skip: Vec<Skip>,
+    /// Variant run instead of the default implementation of the selected parts
+    pub variant: Option<String>,
+    /// Run every variant of the selected parts
+    pub all_variants: bool,
+}
+
+fn filter_from_option(value: Option<&NumberSet>) -> impl Fn(i32) -> bool + '_ {
+    move |target| match value {
+        None => true,
+        Some(value) => value.contains(target),
+    }
+}
+
+/// Solutions matching the selection criteria
+pub struct Selection {
+    /// Every variant of the selected parts
+    pub selected: Vec<&'static Solution>,
+    /// Variants of the selected parts that are run
+    pub wanted: Vec<&'static Solution>,
+}
+
+/// Selects the registered solutions matching the criteria, failing when none of them is run
+pub fn select(criteria: &Criteria) -> Result<Selection> {
+    let matches_year = filter_from_option(criteria.years.as_ref());
+    let matches_day = filter_from_option(criteria.days.as_ref());
+    let matches_part = filter_from_option(criteria.parts.as_ref());
+
+    let mut selected = SOLUTIONS
+        .iter()
+        .filter(|solution| {
+            matches_year(solution.year)
+                && matches_day(solution.day)
+                && matches_part(solution.part)
+                && (criteria.tags.is_empty()
+                    || solution
+                        .metadata
+                        .tags
+                        .iter()
+                        .any(|tag| criteria.tags.contains(tag)))
+                && !criteria.skip.iter().any(|skip| skip.matches(solution))
+        })
+        .collect::<Vec<&Solution>>();
+    if criteria.latest {
+        let latest = selected
+            .iter()
+            .map(|solution| (solution.year, solution.day))
+            .max();
+        selected.retain(|solution| Some((solution.year, solution.day)) == latest);
+    }
+    let wanted = selected
+        .iter()
+        .copied()
+        .filter(|solution| match &criteria.variant {
+            _ if criteria.all_variants => true,
+            Some(variant) => solution.variant == variant,
+            None => solution.is_default_variant(),
+        })
+        .collect::<Vec<&Solution>>();
+
+    if let Some(variant) = &criteria.variant {
+        if wanted.is_empty() && !selected.is_empty() {
+            let mut known = selected
+                .iter()
+                .filter(|solution| !solution.is_default_variant())
+                .map(|solution| solution.variant)
+                .collect::<Vec<&str>>();
+            known.sort();
+            known.dedup();
+            match known.is_empty() {
+                true => bail!("unknown variant {}, the selected parts have none", variant),
+                false => bail!(
+                    "unknown variant {}, the selected parts have: {}",
+                    variant,
+                    known.join(", ")
+                ),
+            }
+        }
+    }
+
+    if wanted.is_empty() {
+        let compiled_out = problems::compiled_out_years()
+            .into_iter()
+            .filter(|year| matches_year(*year))
+            .map(|year| format!("year-{}", year))
+            .collect::<Vec<String>>();
+        match compiled_out.is_empty() || !criteria.tags.is_empty() {
+            true => bail!("no solutions found for the given criteria."),
+            false => bail!(
+                "the solutions for the given criteria were compiled out, enable the {} feature{}",
+                compiled_out.join(", "),
+                if compiled_out.len() > 1 { "s" } else { "" }
+            ),
+        }
+    }
+
+    return Ok(Selection { selected, wanted });
+}
+
+/// Offset of the timezone puzzles are released in, UTC-5, from UTC
+const RELEASE_OFFSET_SECONDS: i64 = -5 * 60 * 60;
+
+/// Last puzzle day of a year, which went from 25 puzzles down to 12 in 2025
+pub fn last_day(year: i32) -> u32 {
+    return match year {
+        ..2025 => 25,
+        _ => 12,
+    };
+}
+
+/// Calendar date, in the proleptic Gregorian calendar
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Date of the given day counted from 1970-01-01, following Howard Hinnant's `civil_from_days`
+    fn from_days(days: i64) -> Date {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        return Date {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32,
+        };
+    }
+
+    /// Date at the given time where puzzles are released
+    pub fn released_at(time: SystemTime) -> Date {
+        let seconds = match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs() as i64),
+        };
+
+        return Date::from_days((seconds + RELEASE_OFFSET_SECONDS).div_euclid(24 * 60 * 60));
+    }
+
+    /// Year and day of the puzzle released on this date, if any
+    pub fn puzzle(&self) -> Option<(i32, i32)> {
+        return match self.month == 12 && (1..=last_day(self.year)).contains(&self.day) {
+            true => Some((self.year, self.day as i32)),
+            false => None,
+        };
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Year and day of the puzzle of the day at the given time, usually now, in the timezone puzzles
+/// are released in
+pub fn today(now: SystemTime) -> Result<(i32, i32)> {
+    let today = Date::released_at(now);
+    return today
+        .puzzle()
+        .ok_or(anyhow!("there is no puzzle today, {} in UTC-5", today));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn set(raw: &str) -> NumberSet {
+        return raw.parse::<NumberSet>().unwrap();
+    }
+
+    fn members(set: &NumberSet, candidates: impl IntoIterator<Item = i32>) -> Vec<i32> {
+        return candidates
+            .into_iter()
+            .filter(|number| set.contains(*number))
+            .collect();
+    }
+
+    #[test]
+    fn number_sets_combine_numbers_and_ranges() {
+        assert_eq!(members(&set("1..=5,9"), 0..=10), [1, 2, 3, 4, 5, 9]);
+        assert_eq!(members(&set(" 2 , 4 "), 0..=10), [2, 4]);
+        assert_eq!(set("7").as_single(), Some(7));
+        assert_eq!(set("7,7..=7").as_single(), Some(7));
+        assert_eq!(set("7,8").as_single(), None);
+    }
+
+    #[test]
+    fn exclusive_ranges_leave_out_their_end() {
+        assert_eq!(members(&set("1..6"), 0..=10), [1, 2, 3, 4, 5]);
+        assert_eq!(set("1..2").as_single(), Some(1));
+    }
+
+    #[test]
+    fn open_ranges_are_unbounded() {
+        let from = set("20..");
+        assert_eq!(members(&from, 18..=22), [20, 21, 22]);
+        assert!(from.contains(i32::MAX));
+
+        let until = set("..3");
+        assert_eq!(members(&until, 0..=5), [0, 1, 2]);
+        assert!(until.contains(i32::MIN));
+        assert!(set("..").contains(0));
+    }
+
+    #[test]
+    fn invalid_number_sets_are_rejected() {
+        for raw in [
+            "5..5",
+            "5..=4",
+            "..-2147483648",
+            "1,,2",
+            "",
+            "1..x",
+            "a",
+            "1...3",
+        ] {
+            assert!(raw.parse::<NumberSet>().is_err(), "{:?} was accepted", raw);
+        }
+    }
+
+    #[test]
+    fn skips_take_a_year_a_day_or_a_part() {
+        let skip = "2020".parse::<Skip>().unwrap();
+        assert_eq!((skip.year, skip.day, skip.part), (2020, None, None));
+
+        let skip = "2020/11".parse::<Skip>().unwrap();
+        assert_eq!((skip.year, skip.day, skip.part), (2020, Some(11), None));
+
+        let skip = "2020/11/2".parse::<Skip>().unwrap();
+        assert_eq!((skip.year, skip.day, skip.part), (2020, Some(11), Some(2)));
+
+        for raw in ["2020/11/2/1", "2020/", "2020/x", "", "2020-11"] {
+            assert!(raw.parse::<Skip>().is_err(), "{:?} was accepted", raw);
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        return Date { year, month, day };
+    }
+
+    #[test]
+    fn days_since_the_epoch_convert_to_dates() {
+        assert_eq!(Date::from_days(0), date(1970, 1, 1));
+        assert_eq!(Date::from_days(-1), date(1969, 12, 31));
+        assert_eq!(Date::from_days(11016), date(2000, 2, 29));
+        assert_eq!(Date::from_days(19782), date(2024, 2, 29));
+        assert_eq!(Date::from_days(19783), date(2024, 3, 1));
+        // 1900 and 2100 are not leap years, unlike 2000
+        assert_eq!(Date::from_days(-25509), date(1900, 2, 28));
+        assert_eq!(Date::from_days(-25508), date(1900, 3, 1));
+        assert_eq!(Date::from_days(47541), date(2100, 3, 1));
+    }
+
+    fn at(seconds: u64) -> SystemTime {
+        return UNIX_EPOCH + Duration::from_secs(seconds);
+    }
+
+    #[test]
+    fn puzzles_are_released_at_midnight_in_utc_minus_5() {
+        // 2020-12-01 05:00 UTC
+        let release = 1606798800;
+        assert_eq!(Date::released_at(at(release)), date(2020, 12, 1));
+        assert_eq!(Date::released_at(at(release - 1)), date(2020, 11, 30));
+
+        assert_eq!(today(at(release)).unwrap(), (2020, 1));
+        assert!(today(at(release - 1)).is_err());
+        assert_eq!(today(at(release + 24 * 24 * 60 * 60)).unwrap(), (2020, 25));
+        assert!(today(at(release + 25 * 24 * 60 * 60)).is_err());
+    }
+
+    #[test]
+    fn puzzles_stop_after_the_last_day_of_their_year() {
+        // 2025-12-12 05:00 UTC
+        let last_release = 1765515600;
+        assert_eq!(today(at(last_release)).unwrap(), (2025, 12));
+        assert!(today(at(last_release + 24 * 60 * 60)).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use std::io::Read;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::metadata::Tag;
+use crate::output::Record;
+use crate::params::{self, Params};
+use crate::problems::{Solution, DEFAULT_VARIANT};
+use crate::sandbox::{run_isolated, SandboxOptions};
+
+/// Largest input accepted by the solve endpoint
+const MAX_INPUT_BYTES: u64 = 16 * 1024 * 1024;
+
+pub struct ServeOptions {
+    pub port: u16,
+    /// Number of inputs solved at the same time
+    pub workers: usize,
+    pub timeout: Duration,
+}
+
+#[derive(Serialize)]
+struct ParamEntry {
+    name: &'static str,
+    default: &'static str,
+}
+
+#[derive(Serialize)]
+struct SolutionEntry {
+    year: i32,
+    day: i32,
+    part: i32,
+    variant: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'static str>,
+    tags: &'static [Tag],
+    params: Vec<ParamEntry>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// Response of an endpoint: an HTTP status code and a JSON body
+struct Reply {
+    status: u16,
+    body: String,
+}
+
+impl Reply {
+    fn json(status: u16, value: &impl Serialize) -> Reply {
+        return match serde_json::to_string(value) {
+            Ok(body) => Reply { status, body },
+            Err(e) => Reply::error(500, format!("could not serialize the response: {}", e)),
+        };
+    }
+
+    fn error(status: u16, error: String) -> Reply {
+        let body = serde_json::to_string(&ErrorBody { error }).unwrap_or(String::from(
+            "{\"error\":\"could not serialize the error\"}",
+        ));
+        return Reply { status, body };
+    }
+}
+
+/// Decodes a percent-encoded query string component, where `+` stands for a space
+fn decode_component(raw: &str) -> Result<String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut raw_bytes = raw.bytes();
+    while let Some(byte) = raw_bytes.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let digits = [raw_bytes.next(), raw_bytes.next()]
+                    .map(|digit| digit.and_then(|digit| (digit as char).to_digit(16)));
+                let value = match digits {
+                    [Some(high), Some(low)] => Some((high * 16 + low) as u8),
+                    _ => None,
+                };
+                bytes.push(value.ok_or(anyhow!("invalid percent-encoding in {}", raw))?);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+
+    return String::from_utf8(bytes).map_err(|_| anyhow!("{} is not valid UTF-8", raw));
+}
+
+/// Splits a query string into the variant to run and the parameter overrides
+fn parse_query(query: &str) -> Result<(String, Params)> {
+    let mut variant = String::from(DEFAULT_VARIANT);
+    let mut params = Params::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let (name, value) = (decode_component(name)?, decode_component(value)?);
+        match name.as_str() {
+            "variant" => variant = value,
+            "" => bail!("empty query parameter name"),
+            _ => {
+                params.insert(name, value);
+            }
+        }
+    }
+
+    return Ok((variant, params));
+}
+
+fn list(solutions: &[&'static Solution]) -> Reply {
+    let entries = solutions
+        .iter()
+        .map(|solution| SolutionEntry {
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            variant: solution.variant,
+            title: solution.metadata.title,
+            tags: solution.metadata.tags,
+            params: solution
+                .params
+                .iter()
+                .map(|param| ParamEntry {
+                    name: param.name(),
+                    default: param.default(),
+                })
+                .collect(),
+        })
+        .collect::<Vec<SolutionEntry>>();
+
+    return Reply::json(200, &entries);
+}
+
+fn solve(
+    request: &mut Request,
+    solutions: &[&'static Solution],
+    path: [&str; 3],
+    query: &str,
+    options: &ServeOptions,
+) -> Reply {
+    let numbers = path.map(|raw| raw.parse::<i32>().ok());
+    let (year, day, part) = match numbers {
+        [Some(year), Some(day), Some(part)] => (year, day, part),
+        _ => {
+            return Reply::error(
+                400,
+                format!("expected /solve/year/day/part, found {}", path.join("/")),
+            )
+        }
+    };
+    let (variant, params) = match parse_query(query) {
+        Ok(query) => query,
+        Err(e) => return Reply::error(400, e.to_string()),
+    };
+
+    let solution = match solutions.iter().find(|solution| {
+        (solution.year, solution.day, solution.part) == (year, day, part)
+            && solution.variant == variant
+    }) {
+        Some(solution) => solution,
+        None if variant == DEFAULT_VARIANT => {
+            return Reply::error(
+                404,
+                format!("no solution registered for {}/{} P{}", year, day, part),
+            )
+        }
+        None => {
+            return Reply::error(
+                404,
+                format!(
+                    "no variant {} registered for {}/{} P{}",
+                    variant, year, day, part
+                ),
+            )
+        }
+    };
+    if let Err(e) = params::check(&[solution], &params) {
+        return Reply::error(400, format!("{:#}", e));
+    }
+
+    let mut input = String::new();
+    let read = request
+        .as_reader()
+        .take(MAX_INPUT_BYTES + 1)
+        .read_to_string(&mut input);
+    match read {
+        Ok(read) if read as u64 > MAX_INPUT_BYTES => {
+            return Reply::error(
+                413,
+                format!("inputs are limited to {} bytes", MAX_INPUT_BYTES),
+            )
+        }
+        Ok(_) => {}
+        Err(e) => return Reply::error(400, format!("could not read the input: {}", e)),
+    }
+
+    // solutions run in a child process, so that a stuck or crashing one only fails its own request
+    let sandbox_options = SandboxOptions {
+        timeout: options.timeout,
+        memory_limit: None,
+        cpu_limit: None,
+    };
+    let (result, time, parse) = run_isolated(solution, &input, &params, &sandbox_options);
+    let mut record = Record::new(solution, None, result, time, None);
+    if let Some(parse) = parse {
+        record = record.with_parse(parse);
+    }
+
+    return match record.to_json() {
+        Ok(body) => Reply { status: 200, body },
+        Err(e) => Reply::error(500, format!("could not serialize the record: {}", e)),
+    };
+}
+
+fn route(request: &mut Request, solutions: &[&'static Solution], options: &ServeOptions) -> Reply {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let segments = path.trim_matches('/').split('/').collect::<Vec<&str>>();
+
+    return match (request.method(), segments.as_slice()) {
+        (Method::Get, ["health"]) => Reply::json(200, &serde_json::json!({ "status": "ok" })),
+        (Method::Get, ["solutions"]) => list(solutions),
+        (Method::Post, ["solve", year, day, part]) => {
+            solve(request, solutions, [year, day, part], query, options)
+        }
+        (method, ["health"] | ["solutions"] | ["solve", _, _, _]) => {
+            Reply::error(405, format!("{} is not allowed on {}", method, path))
+        }
+        _ => Reply::error(404, format!("no endpoint at {}", path)),
+    };
+}
+
+fn respond(mut request: Request, solutions: &[&'static Solution], options: &ServeOptions) {
+    let reply = route(&mut request, solutions, options);
+    eprintln!("{} {} | {}", request.method(), request.url(), reply.status);
+
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("the content type header is valid");
+    let response = Response::from_string(reply.body)
+        .with_status_code(reply.status)
+        .with_header(content_type);
+    if let Err(e) = request.respond(response) {
+        eprintln!("could not send the response: {}", e);
+    }
+}
+
+/// Answers requests on localhost until the process is stopped: `GET /health`, `GET /solutions`
+/// and `POST /solve/{year}/{day}/{part}`, taking the input as the body and optionally the variant
+/// and parameters in the query string, such as `?variant=window&preamble_len=5`
+pub fn serve(solutions: &[&'static Solution], options: &ServeOptions) -> Result<()> {
+    let server = Server::http(("127.0.0.1", options.port))
+        .map_err(|e| anyhow!("could not listen on port {}: {}", options.port, e))?;
+    eprintln!("listening on http://127.0.0.1:{}", options.port);
+
+    // inputs are solved by the workers, so that slow solutions never hold up the other endpoints,
+    // which are answered right away
+    let (sender, receiver) = mpsc::channel::<Request>();
+    let receiver = Mutex::new(receiver);
+    thread::scope(|scope| {
+        for _ in 0..options.workers.max(1) {
+            scope.spawn(|| loop {
+                let received = match receiver.lock() {
+                    Ok(receiver) => receiver.recv(),
+                    Err(_) => return,
+                };
+                match received {
+                    Ok(request) => respond(request, solutions, options),
+                    Err(_) => return,
+                }
+            });
+        }
+
+        for request in server.incoming_requests() {
+            match request.method() {
+                Method::Post => {
+                    if let Err(mpsc::SendError(request)) = sender.send(request) {
+                        respond(request, solutions, options);
+                    }
+                }
+                _ => respond(request, solutions, options),
+            }
+        }
+        drop(sender);
+    });
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components_are_percent_decoded() {
+        assert_eq!(decode_component("preamble_len").unwrap(), "preamble_len");
+        assert_eq!(decode_component("a+b%20c").unwrap(), "a b c");
+        assert_eq!(decode_component("%2B%2b%25").unwrap(), "++%");
+        assert_eq!(decode_component("%C3%A9").unwrap(), "é");
+    }
+
+    #[test]
+    fn invalid_percent_encodings_are_rejected() {
+        for raw in ["%zz", "%", "a%", "a%2", "%+1", "%C3"] {
+            assert!(decode_component(raw).is_err(), "{:?} was accepted", raw);
+        }
+    }
+
+    #[test]
+    fn queries_give_the_variant_and_parameters() {
+        let (variant, params) = parse_query("").unwrap();
+        assert_eq!(variant, DEFAULT_VARIANT);
+        assert!(params.is_empty());
+
+        let (variant, params) =
+            parse_query("variant=window&preamble_len=5&&label=a+b&flag").unwrap();
+        assert_eq!(variant, "window");
+        assert_eq!(
+            params.into_iter().collect::<Vec<(String, String)>>(),
+            [
+                (String::from("flag"), String::new()),
+                (String::from("label"), String::from("a b")),
+                (String::from("preamble_len"), String::from("5")),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_queries_are_rejected() {
+        for query in ["=5", "a=1&=2", "a=%zz", "%=1", "a=%"] {
+            assert!(parse_query(query).is_err(), "{:?} was accepted", query);
+        }
+    }
+}
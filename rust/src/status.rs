@@ -0,0 +1,175 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::answers::ExpectedAnswers;
+use crate::baseline::Baseline;
+use crate::input::InputResolver;
+use crate::problems::Solution;
+use crate::selection::last_day;
+use crate::timing::prettify_duration;
+
+const DAYS_PER_ROW: usize = 5;
+
+/// How far a part is according to the registry and the expected answers
+#[derive(Clone, Copy, PartialEq)]
+enum PartProgress {
+    Missing,
+    Implemented,
+    /// Implemented with a known answer, which is what earns its star
+    Verified,
+}
+
+impl PartProgress {
+    fn symbol(&self) -> char {
+        return match self {
+            PartProgress::Missing => '.',
+            PartProgress::Implemented => '+',
+            PartProgress::Verified => '*',
+        };
+    }
+}
+
+struct DayCell {
+    day: i32,
+    parts: [PartProgress; 2],
+    input: bool,
+    /// Last known time of both parts on the real input
+    time: Option<Duration>,
+}
+
+struct YearCalendar {
+    year: i32,
+    days: Vec<DayCell>,
+}
+
+impl YearCalendar {
+    fn stars(&self) -> usize {
+        return self
+            .days
+            .iter()
+            .flat_map(|cell| cell.parts.iter())
+            .filter(|part| **part == PartProgress::Verified)
+            .count();
+    }
+}
+
+/// Progress over every day of the selected years
+pub struct Calendar {
+    years: Vec<YearCalendar>,
+}
+
+impl Calendar {
+    pub fn new(
+        solutions: &[&Solution],
+        years: &[i32],
+        expected_answers: &ExpectedAnswers,
+        input_resolver: &InputResolver,
+        last_timings: &Baseline,
+    ) -> Calendar {
+        let mut calendars: Vec<YearCalendar> = Vec::new();
+        for year in years.iter().copied() {
+            let last = last_day(year) as i32;
+            let mut days: Vec<DayCell> = Vec::new();
+            for day in 1..=last {
+                let mut parts = [PartProgress::Missing; 2];
+                for solution in solutions.iter().filter(|solution| {
+                    (solution.year, solution.day) == (year, day) && solution.is_default_variant()
+                }) {
+                    let progress = match expected_answers.for_solution(solution) {
+                        Some(_) => PartProgress::Verified,
+                        None => PartProgress::Implemented,
+                    };
+                    if let Some(slot) = parts.get_mut((solution.part - 1) as usize) {
+                        *slot = progress;
+                    }
+                }
+
+                let timings = last_timings.entries.iter().filter(|entry| {
+                    (entry.year, entry.day) == (year, day)
+                        && entry.variant.is_none()
+                        && entry.example.is_none()
+                });
+                let time = timings
+                    .map(|entry| Duration::from_nanos(entry.wall_ns))
+                    .reduce(|total, time| total + time);
+
+                days.push(DayCell {
+                    day,
+                    parts,
+                    input: input_resolver.input_path(year, day).is_ok(),
+                    time,
+                });
+            }
+
+            // the last day has a single puzzle, its second star being given for all the others
+            let mut calendar = YearCalendar { year, days };
+            if let Some(last_cell) = calendar.days.last_mut() {
+                last_cell.parts[1] = PartProgress::Missing;
+            }
+            if calendar.stars() == 2 * last as usize - 1 {
+                if let Some(last_cell) = calendar.days.last_mut() {
+                    last_cell.parts[1] = PartProgress::Verified;
+                }
+            }
+            calendars.push(calendar);
+        }
+
+        return Calendar { years: calendars };
+    }
+}
+
+impl fmt::Display for DayCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = match self.time {
+            Some(time) => prettify_duration(time),
+            None => String::new(),
+        };
+
+        write!(
+            f,
+            "{:>2} {}{} {} {:>6}",
+            self.day,
+            self.parts[0].symbol(),
+            self.parts[1].symbol(),
+            if self.input { 'I' } else { '-' },
+            time
+        )
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "* solved with a known answer · + solved · . missing · I input found · - no input"
+        )?;
+
+        let mut total_stars: usize = 0;
+        let mut total_possible: usize = 0;
+        for calendar in self.years.iter() {
+            let possible = 2 * calendar.days.len();
+            total_stars += calendar.stars();
+            total_possible += possible;
+
+            writeln!(f)?;
+            writeln!(
+                f,
+                "⭐ | {}: {}/{}",
+                calendar.year,
+                calendar.stars(),
+                possible
+            )?;
+            for row in calendar.days.chunks(DAYS_PER_ROW) {
+                let cells = row.iter().map(DayCell::to_string).collect::<Vec<String>>();
+                writeln!(f, "   | {}", cells.join(" │ ").trim_end())?;
+            }
+        }
+
+        if self.years.len() > 1 {
+            writeln!(f)?;
+            writeln!(f, "⭐ | total: {}/{}", total_stars, total_possible)?;
+        }
+
+        return Ok(());
+    }
+}
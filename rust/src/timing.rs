@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use crate::allocations::{self, AllocationStats};
+
+#[derive(Clone, Copy, Default)]
+pub struct Measurement {
+    pub wall: Duration,
+    pub cpu: Duration,
+    pub allocations: Option<AllocationStats>,
+}
+
+impl std::ops::AddAssign for Measurement {
+    fn add_assign(&mut self, other: Self) {
+        self.wall += other.wall;
+        self.cpu += other.cpu;
+        self.allocations = match (self.allocations, other.allocations) {
+            (Some(current), Some(other)) => Some(current.merge(other)),
+            (current, other) => current.or(other),
+        };
+    }
+}
+
+fn thread_cpu_time() -> Duration {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: `time` is a valid, writable timespec and the clock id is a constant known to the kernel
+    let outcome = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    if outcome != 0 {
+        return Duration::ZERO;
+    }
+
+    return Duration::new(time.tv_sec as u64, time.tv_nsec as u32);
+}
+
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Measurement) {
+    let cpu_start = thread_cpu_time();
+    let wall_start = Instant::now();
+
+    let (result, allocations) = allocations::track(f);
+
+    let wall = wall_start.elapsed();
+    let cpu = thread_cpu_time().saturating_sub(cpu_start);
+
+    return (
+        result,
+        Measurement {
+            wall,
+            cpu,
+            allocations,
+        },
+    );
+}
+
+pub fn prettify_duration(value: Duration) -> String {
+    if value.as_nanos() < 2500 {
+        format!("{}ns", value.as_nanos())
+    } else if value.as_micros() < 2500 {
+        format!("{}μs", value.as_micros())
+    } else if value.as_millis() < 2500 {
+        format!("{}ms", value.as_millis())
+    } else {
+        format!("{}s", value.as_secs())
+    }
+}
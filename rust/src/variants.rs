@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::output::Record;
+use crate::problems::DEFAULT_VARIANT;
+use crate::timing::prettify_duration;
+
+struct VariantRun {
+    variant: String,
+    answer: Option<Answer>,
+    wall: Option<Duration>,
+    cpu: Option<Duration>,
+}
+
+/// Runs of every variant of a part on the same input
+struct VariantGroup {
+    label: String,
+    runs: Vec<VariantRun>,
+}
+
+impl VariantGroup {
+    /// Whether every variant produced the same answer, a variant failing where another one answered
+    /// counting as a disagreement
+    fn agrees(&self) -> bool {
+        let mut answers = self.runs.iter().map(|run| run.answer.as_ref());
+        let first = answers.next().flatten();
+        return answers.all(|answer| match (answer, first) {
+            (Some(answer), Some(first)) => answer.matches(first),
+            (answer, first) => answer.is_none() && first.is_none(),
+        });
+    }
+}
+
+/// Collects the records of the variants of each part, to check that they agree and compare their timings
+#[derive(Default)]
+pub struct VariantComparison {
+    groups: BTreeMap<(i32, i32, i32, Option<String>), VariantGroup>,
+}
+
+impl VariantComparison {
+    pub fn add(&mut self, record: &Record) {
+        let mut label = format!("{}/{} P{}", record.year, record.day, record.part);
+        if let Some(example) = &record.example {
+            label.push_str(&format!(" [{}]", example));
+        }
+
+        self.groups
+            .entry((record.year, record.day, record.part, record.example.clone()))
+            .or_insert(VariantGroup {
+                label,
+                runs: Vec::new(),
+            })
+            .runs
+            .push(VariantRun {
+                variant: record.variant.clone().unwrap_or(DEFAULT_VARIANT.into()),
+                answer: record.answer.clone(),
+                wall: record.time.map(|time| time.wall),
+                cpu: record.time.map(|time| time.cpu),
+            });
+    }
+
+    /// Whether the variants of every part agree on the answer
+    pub fn agrees(&self) -> bool {
+        return self.groups.values().all(VariantGroup::agrees);
+    }
+
+    /// Whether no part was run with more than one variant
+    pub fn is_empty(&self) -> bool {
+        return self.compared().next().is_none();
+    }
+
+    /// Parts that were run with more than one variant, in order
+    fn compared(&self) -> impl Iterator<Item = &VariantGroup> {
+        return self.groups.values().filter(|group| group.runs.len() > 1);
+    }
+}
+
+impl fmt::Display for VariantComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .compared()
+            .flat_map(|group| group.runs.iter().map(|run| run.variant.len()))
+            .max()
+            .unwrap_or(0);
+
+        for (index, group) in self.compared().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            let indicator = match group.agrees() {
+                true => "⚖️",
+                false => "❗",
+            };
+            writeln!(f, "{} | {}", indicator, group.label)?;
+            if !group.agrees() {
+                writeln!(f, "   | the variants disagree on the answer")?;
+            }
+
+            let reference = group
+                .runs
+                .iter()
+                .find(|run| run.variant == DEFAULT_VARIANT)
+                .and_then(|run| run.wall);
+            for run in group.runs.iter() {
+                let timing = match (run.wall, run.cpu) {
+                    (Some(wall), Some(cpu)) => format!(
+                        "{:>10} (cpu {:>10})",
+                        prettify_duration(wall),
+                        prettify_duration(cpu)
+                    ),
+                    _ => format!("{:>26}", "-"),
+                };
+                let speedup = match (reference, run.wall) {
+                    (Some(reference), Some(wall)) if !wall.is_zero() => {
+                        format!("{:>7.2}x", reference.as_secs_f64() / wall.as_secs_f64())
+                    }
+                    _ => format!("{:>8}", "-"),
+                };
+                let answer = match &run.answer {
+                    Some(answer) if answer.is_multiline() => "(grid)".into(),
+                    Some(answer) => answer.to_string(),
+                    None => "no answer".into(),
+                };
+                writeln!(
+                    f,
+                    "   | {:<width$}  {}  {}  {}",
+                    run.variant,
+                    timing,
+                    speedup,
+                    answer,
+                    width = width
+                )?;
+            }
+        }
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use crate::answer::Answer;
+use crate::answers::ExpectedAnswers;
+use crate::input::InputResolver;
+use crate::output::{Record, Status};
+use crate::problems::{Solution, DEFAULT_VARIANT};
+use crate::selection::last_day;
+
+/// Parts of a day, the last day of a year having a single puzzle
+fn expected_parts(year: i32, day: i32) -> &'static [i32] {
+    return match day == last_day(year) as i32 {
+        true => &[1],
+        false => &[1, 2],
+    };
+}
+
+/// Problems found while verifying, grouped by category and kept in the order categories were first hit
+#[derive(Default)]
+pub struct Verification {
+    problems: Vec<(&'static str, Vec<String>)>,
+    inputs_checked: bool,
+}
+
+impl Verification {
+    fn add(&mut self, category: &'static str, problem: String) {
+        match self
+            .problems
+            .iter_mut()
+            .find(|(existing, _)| *existing == category)
+        {
+            Some((_, problems)) => problems.push(problem),
+            None => self.problems.push((category, vec![problem])),
+        }
+    }
+
+    pub fn check_registry(
+        &mut self,
+        registry: &[Solution],
+        wanted: &[&Solution],
+        expected_answers: &ExpectedAnswers,
+    ) {
+        let mut registrations: BTreeMap<(i32, i32, i32, &str), usize> = BTreeMap::new();
+        for solution in registry {
+            *registrations
+                .entry((solution.year, solution.day, solution.part, solution.variant))
+                .or_default() += 1;
+        }
+
+        let mut days: Vec<(i32, i32)> = wanted.iter().map(|s| (s.year, s.day)).collect();
+        days.dedup();
+
+        for (year, day) in days.iter() {
+            for part in expected_parts(*year, *day) {
+                if !registrations.contains_key(&(*year, *day, *part, DEFAULT_VARIANT)) {
+                    self.add(
+                        "missing parts",
+                        format!("{}/{} P{} is not registered", year, day, part),
+                    );
+                }
+            }
+        }
+
+        for ((year, day, part, variant), count) in registrations.iter() {
+            if *count == 1 || !days.contains(&(*year, *day)) {
+                continue;
+            }
+            let variant = match *variant == DEFAULT_VARIANT {
+                true => String::new(),
+                false => format!(" ({})", variant),
+            };
+            self.add(
+                "duplicate registrations",
+                format!(
+                    "{}/{} P{}{} is registered {} times",
+                    year, day, part, variant, count
+                ),
+            );
+        }
+
+        for solution in wanted.iter().filter(|solution| {
+            solution.is_default_variant() && expected_answers.for_solution(solution).is_none()
+        }) {
+            self.add(
+                "missing expected answers",
+                format!(
+                    "{}/{} P{} has no expected answer",
+                    solution.year, solution.day, solution.part
+                ),
+            );
+        }
+    }
+
+    pub fn check_inputs(&mut self, wanted: &[&Solution], input_resolver: &InputResolver) {
+        self.inputs_checked = true;
+        let mut days: Vec<(i32, i32)> = wanted.iter().map(|s| (s.year, s.day)).collect();
+        days.dedup();
+
+        for (year, day) in days {
+            if let Err(e) = input_resolver.input_path(year, day) {
+                self.add("missing inputs", e.to_string());
+            }
+        }
+    }
+
+    pub fn check_record(&mut self, record: &Record) {
+        // solutions that could not run because of a missing input were already reported by the input check
+        if record.time.is_none() && self.inputs_checked {
+            return;
+        }
+
+        let label = record.label();
+
+        match record.status {
+            Status::Correct | Status::Unknown => {}
+            Status::Wrong => self.add(
+                "wrong answers",
+                format!(
+                    "{}: got {} but expected {}",
+                    label,
+                    record
+                        .answer
+                        .as_ref()
+                        .map(Answer::to_string)
+                        .unwrap_or_default(),
+                    record
+                        .expected
+                        .as_ref()
+                        .map(Answer::to_string)
+                        .unwrap_or_default()
+                ),
+            ),
+            status => self.add(
+                "errors",
+                format!(
+                    "{}: {} ({})",
+                    label,
+                    record.error.as_deref().unwrap_or_default(),
+                    status.name()
+                ),
+            ),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        return self.problems.is_empty();
+    }
+
+    pub fn print(&self) {
+        if self.passed() {
+            eprintln!("\n✅ | verification passed");
+            return;
+        }
+
+        let count: usize = self
+            .problems
+            .iter()
+            .map(|(_, problems)| problems.len())
+            .sum();
+        eprintln!("\n🔎 | verification found {} problems", count);
+        for (category, problems) in self.problems.iter() {
+            eprintln!("\n{} ({})", category, problems.len());
+            for problem in problems {
+                eprintln!("   | {}", problem);
+            }
+        }
+    }
+}
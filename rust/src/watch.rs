@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Result};
+
+use crate::answer::Answer;
+use crate::baseline::{Baseline, BaselineEntry};
+use crate::environment::Environment;
+use crate::examples::Examples;
+use crate::output::{reporter, OutputFormat, Record};
+use crate::panics;
+use crate::problems::Solution;
+use crate::runner::{self, check_run, collect_jobs, update_last_timings, RunSettings};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// Time given to an editor to finish writing a file before it is read
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// Modification time and size of a file, or `None` when it does not exist
+type FileState = Option<(SystemTime, u64)>;
+
+fn file_state(path: &Path) -> FileState {
+    let metadata = fs::metadata(path).ok()?;
+    return Some((metadata.modified().ok()?, metadata.len()));
+}
+
+/// Polls files for changes, comparing their modification time and size with those last seen
+pub struct FileWatcher {
+    files: Vec<(PathBuf, FileState)>,
+}
+
+impl FileWatcher {
+    pub fn new(mut paths: Vec<PathBuf>) -> FileWatcher {
+        paths.sort();
+        paths.dedup();
+
+        return FileWatcher {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let state = file_state(&path);
+                    (path, state)
+                })
+                .collect(),
+        };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.files.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.files.is_empty();
+    }
+
+    /// Blocks until at least one of the files is created, modified or removed, returning those that were
+    pub fn wait_for_change(&mut self) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let changed = self
+                .files
+                .iter()
+                .filter(|(path, state)| file_state(path) != *state)
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<PathBuf>>();
+            if changed.is_empty() {
+                continue;
+            }
+
+            thread::sleep(SETTLE_TIME);
+            for (path, state) in self.files.iter_mut() {
+                *state = file_state(path);
+            }
+            return changed;
+        }
+    }
+}
+
+pub fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+    let _ = io::stdout().flush();
+}
+
+/// Answers of the last two runs, to show how the answers changed after an edit
+#[derive(Default)]
+pub struct AnswerHistory {
+    previous: HashMap<String, Option<Answer>>,
+    current: Vec<(String, Option<Answer>)>,
+}
+
+impl AnswerHistory {
+    pub fn add(&mut self, record: &Record) {
+        self.current.push((record.label(), record.answer.clone()));
+    }
+
+    /// Whether a previous run produced answers to compare against
+    pub fn has_previous(&self) -> bool {
+        return !self.previous.is_empty();
+    }
+
+    /// Makes the answers of the run that just ended the previous ones
+    pub fn end_run(&mut self) {
+        self.previous = self.current.drain(..).collect();
+    }
+}
+
+fn describe(answer: &Option<Answer>) -> String {
+    return match answer {
+        Some(answer) if answer.is_multiline() => String::from("(grid)"),
+        Some(answer) => answer.to_string(),
+        None => String::from("no answer"),
+    };
+}
+
+impl fmt::Display for AnswerHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "⏪ | previous run")?;
+        for (label, answer) in self.current.iter() {
+            let previous = match self.previous.get(label) {
+                Some(previous) => previous,
+                None => {
+                    writeln!(f, "   | {}: new, {}", label, describe(answer))?;
+                    continue;
+                }
+            };
+
+            let unchanged = match (previous, answer) {
+                (Some(previous), Some(answer)) => answer.matches(previous),
+                (previous, answer) => previous.is_none() && answer.is_none(),
+            };
+            match unchanged {
+                true => writeln!(f, "   | {}: {} (unchanged)", label, describe(answer))?,
+                false => writeln!(
+                    f,
+                    "   | {}: {} → {}",
+                    label,
+                    describe(previous),
+                    describe(answer)
+                )?,
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+/// Reruns the solutions whenever one of the files they read, or one of the `sources` they are built
+/// from, changes, showing how their answers changed since the previous run. Only returns when the
+/// solutions cannot be run
+pub fn watch(
+    solutions: &[&Solution],
+    environment: &Environment,
+    settings: &RunSettings,
+    sources: &[PathBuf],
+) -> Result<Infallible> {
+    check_run(solutions, environment, settings)?;
+    if environment.input_resolver.reads_stdin() {
+        bail!("standard input cannot be watched, give an input file");
+    }
+
+    panics::install_hook();
+
+    let mut days: Vec<(i32, i32)> = solutions.iter().map(|s| (s.year, s.day)).collect();
+    days.dedup();
+
+    let input_resolver = &environment.input_resolver;
+    let mut history = AnswerHistory::default();
+    let mut changed: Vec<PathBuf> = Vec::new();
+    loop {
+        // the files are listed again on every run, as an examples manifest may list new inputs
+        let mut watched = sources.to_vec();
+        for (year, day) in days.iter() {
+            match settings.use_examples {
+                true => watched.extend(Examples::watched_files(input_resolver, *year, *day)),
+                false => watched.extend(input_resolver.watched_input(*year, *day)),
+            }
+        }
+        let mut watcher = FileWatcher::new(watched);
+
+        let mut header = vec![format!(
+            "👀 | watching {} files, press ctrl-c to stop",
+            watcher.len()
+        )];
+        for path in changed.iter() {
+            header.push(match sources.contains(path) {
+                true => format!(
+                    "   | {} changed, rebuild for the change to take effect",
+                    path.display()
+                ),
+                false => format!("   | {} changed", path.display()),
+            });
+        }
+        match settings.output {
+            OutputFormat::Human => {
+                clear_screen();
+                header.iter().for_each(|line| println!("{}", line));
+                println!();
+            }
+            _ => header.iter().for_each(|line| eprintln!("{}", line)),
+        }
+
+        let jobs = collect_jobs(
+            solutions,
+            input_resolver,
+            &environment.expected_answers,
+            &settings.params,
+            settings.use_examples,
+        );
+        let mut reporter = reporter(settings.output);
+        let mut timings = Baseline::default();
+        let summary = runner::run(jobs, settings.jobs, &settings.execution, |record| {
+            timings.entries.extend(BaselineEntry::from_record(record));
+            history.add(record);
+            reporter.report(record);
+        });
+        reporter.finish(&summary);
+        update_last_timings(timings, environment, &settings.params);
+
+        if history.has_previous() {
+            match settings.output {
+                OutputFormat::Human => println!("\n{}", history),
+                _ => eprint!("{}", history),
+            }
+        }
+        history.end_run();
+
+        changed = watcher.wait_for_change();
+    }
+}
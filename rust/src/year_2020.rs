@@ -1,12 +0,0 @@
-pub mod problem_1;
-pub mod problem_10;
-pub mod problem_11;
-pub mod problem_12;
-pub mod problem_2;
-pub mod problem_3;
-pub mod problem_4;
-pub mod problem_5;
-pub mod problem_6;
-pub mod problem_7;
-pub mod problem_8;
-pub mod problem_9;
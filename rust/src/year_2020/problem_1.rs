@@ -2,7 +2,21 @@ use std::collections::HashSet;
 
 use anyhow::{bail, Result};
 
-fn parse_input(input: String) -> Vec<i32> {
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::Param;
+use crate::solution;
+
+solution!(
+    Problem1,
+    part_1 = 440979,
+    part_2 = 82498112;
+    title = "Report Repair";
+    tags = [Search];
+    params = [TARGET]
+);
+
+fn parse_input(input: &str) -> Vec<i32> {
     return input
         .split("\n")
         .map(str::parse::<i32>)
@@ -10,33 +24,43 @@ fn parse_input(input: String) -> Vec<i32> {
         .collect();
 }
 
-const TARGET: i32 = 2020;
+static TARGET: Param<i32> = Param::new("target", "2020");
+
+pub struct Problem1;
 
-pub fn part_1(input: String) -> Result<String> {
-    let numbers = parse_input(input);
+impl Day for Problem1 {
+    type Parsed = Vec<i32>;
 
-    let mut seen: HashSet<i32> = HashSet::new();
-    for current in numbers.iter() {
-        match seen.get(&(TARGET - current)) {
-            Some(corresponding) => return Ok((current * corresponding).to_string()),
-            None => seen.insert(*current),
-        };
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        return Ok(parse_input(input));
     }
 
-    bail!("no valid pair found");
-}
+    fn part_1(numbers: &Vec<i32>) -> Result<Answer> {
+        let target = TARGET.get()?;
+
+        let mut seen: HashSet<i32> = HashSet::new();
+        for current in numbers.iter() {
+            match seen.get(&(target - current)) {
+                Some(corresponding) => return Ok((current * corresponding).into()),
+                None => seen.insert(*current),
+            };
+        }
+
+        bail!("no valid pair found");
+    }
 
-pub fn part_2(input: String) -> Result<String> {
-    let parsed = parse_input(input);
-    let numbers: HashSet<&i32> = HashSet::from_iter(parsed.iter());
+    fn part_2(parsed: &Vec<i32>) -> Result<Answer> {
+        let target = TARGET.get()?;
+        let numbers: HashSet<&i32> = HashSet::from_iter(parsed.iter());
 
-    for n1 in numbers.iter() {
-        for n2 in numbers.iter() {
-            if let Some(n3) = numbers.get(&(TARGET - *n1 - *n2)) {
-                return Ok((*n1 * *n2 * *n3).to_string());
+        for n1 in numbers.iter() {
+            for n2 in numbers.iter() {
+                if let Some(n3) = numbers.get(&(target - *n1 - *n2)) {
+                    return Ok((*n1 * *n2 * *n3).into());
+                }
             }
         }
-    }
 
-    bail!("no valid triplet found");
+        bail!("no valid triplet found");
+    }
 }
@@ -2,6 +2,18 @@ use std::collections::HashMap;
 
 use anyhow::{anyhow, bail, ensure, Result};
 
+use crate::answer::Answer;
+use crate::solution;
+
+solution!(
+    raw,
+    part_1 = 2400,
+    part_2 = 338510590509056;
+    title = "Adapter Array";
+    tags = [Dp];
+    notes = "part 2 memoizes the number of arrangements reachable from each adapter"
+);
+
 fn parse_input(input: String) -> Result<Vec<i64>> {
     return Result::from_iter(
         input
@@ -12,7 +24,7 @@ fn parse_input(input: String) -> Result<Vec<i64>> {
     );
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let mut numbers = parse_input(input)?;
     numbers.push(0);
     numbers.sort();
@@ -31,7 +43,7 @@ pub fn part_1(input: String) -> Result<String> {
         }
     }
 
-    return Ok((one_differences * (three_differences + 1)).to_string());
+    return Ok((one_differences * (three_differences + 1)).into());
 }
 
 fn lookup_or_compute(
@@ -86,10 +98,10 @@ fn compute_combinations(
     return Ok(first_near_combinations + second_near_combinations + third_near_combinations);
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let mut numbers = parse_input(input)?;
     numbers.push(0);
     numbers.sort();
 
-    return Ok(compute_combinations(0, &numbers, &mut HashMap::new())?.to_string());
+    return Ok(compute_combinations(0, &numbers, &mut HashMap::new())?.into());
 }
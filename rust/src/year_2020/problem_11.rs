@@ -3,8 +3,19 @@ use std::fmt::Display;
 use anyhow::Result;
 use thiserror::Error;
 
+use crate::answer::Answer;
 use crate::ext::matrix::{self, WalkUntilOpts};
 use crate::ext::traits::{adjacent::Adjacent, empty_items::EmptyItems};
+use crate::solution;
+
+solution!(
+    raw,
+    part_1 = 2438,
+    part_2 = 2174;
+    title = "Seating System";
+    tags = [Grid, Simulation];
+    notes = "part 2 walks each direction with ext::matrix until the first seat"
+);
 
 #[derive(Clone, Debug)]
 enum LayoutCell {
@@ -180,16 +191,16 @@ impl TryFrom<String> for SeatsLayout {
     }
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let layout = SeatsLayout::try_from(input)?;
     let stabilized = layout.stabilized(true);
 
-    return Ok(stabilized.count_seats(true).to_string());
+    return Ok(stabilized.count_seats(true).into());
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let layout = SeatsLayout::try_from(input)?;
     let stabilized = layout.stabilized(false);
 
-    return Ok(stabilized.count_seats(true).to_string());
+    return Ok(stabilized.count_seats(true).into());
 }
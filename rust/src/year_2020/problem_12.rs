@@ -1,7 +1,17 @@
 use anyhow::{anyhow, bail, ensure, Result};
 use thiserror::Error;
 
+use crate::answer::Answer;
 use crate::ext::traits::empty_items::EmptyItems;
+use crate::solution;
+
+solution!(
+    raw,
+    part_1 = 2297,
+    part_2 = 89984;
+    title = "Rain Risk";
+    tags = [Simulation]
+);
 
 enum Instruction {
     North(i32),
@@ -111,7 +121,7 @@ fn parse_input(input: String) -> Result<Vec<Instruction>, InputParseError> {
     }));
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let instructions = parse_input(input)?;
 
     let mut coordinates = (0, 0);
@@ -137,7 +147,7 @@ pub fn part_1(input: String) -> Result<String> {
         }
     }
 
-    return Ok((coordinates.0.abs() + coordinates.1.abs()).to_string());
+    return Ok((coordinates.0.abs() + coordinates.1.abs()).into());
 }
 
 fn relative_turn(
@@ -193,7 +203,7 @@ fn relative_turn(
     });
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let instructions = parse_input(input)?;
 
     let mut waypoint_offset = (1, 10);
@@ -217,5 +227,5 @@ pub fn part_2(input: String) -> Result<String> {
         }
     }
 
-    return Ok((coordinates.0.abs() + coordinates.1.abs()).to_string());
+    return Ok((coordinates.0.abs() + coordinates.1.abs()).into());
 }
@@ -1,6 +1,17 @@
 use anyhow::{Error, Result};
 use thiserror::Error;
 
+use crate::answer::Answer;
+use crate::solution;
+
+solution!(
+    raw,
+    part_1 = 517,
+    part_2 = 284;
+    title = "Password Philosophy";
+    tags = [Parsing]
+);
+
 struct Entry {
     n1: i32,
     n2: i32,
@@ -72,7 +83,7 @@ fn parse_input(input: String) -> Result<Vec<Entry>> {
     );
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let entries = parse_input(input)?;
     let valid: Vec<&Entry> = entries
         .iter()
@@ -90,10 +101,10 @@ pub fn part_1(input: String) -> Result<String> {
         })
         .collect();
 
-    return Ok(valid.len().to_string());
+    return Ok(valid.len().into());
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let entries = parse_input(input)?;
     let valid: Vec<&Entry> = entries
         .iter()
@@ -113,5 +124,5 @@ pub fn part_2(input: String) -> Result<String> {
         })
         .collect();
 
-    return Ok(valid.len().to_string());
+    return Ok(valid.len().into());
 }
@@ -3,6 +3,17 @@ use std::iter::Product;
 use anyhow::Result;
 use thiserror::Error;
 
+use crate::answer::Answer;
+use crate::solution;
+
+solution!(
+    raw,
+    part_1 = 211,
+    part_2 = 3584591857;
+    title = "Toboggan Trajectory";
+    tags = [Grid]
+);
+
 enum Tile {
     Free,
     Tree,
@@ -89,7 +100,7 @@ impl TryFrom<String> for Area {
     }
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let area = Area::try_from(input)?;
 
     return Ok(area
@@ -100,10 +111,10 @@ pub fn part_1(input: String) -> Result<String> {
                 y_increment: 1,
             },
         )
-        .to_string());
+        .into());
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let area = Area::try_from(input)?;
     let slopes = [
         Slope {
@@ -133,5 +144,5 @@ pub fn part_2(input: String) -> Result<String> {
             .map(|slope| area.count_trees(Point { x: 0, y: 0 }, slope))
             .into_iter(),
     )
-    .to_string());
+    .into());
 }
@@ -1,7 +1,17 @@
 use anyhow::Result;
 use thiserror::Error;
 
+use crate::answer::Answer;
 use crate::ext;
+use crate::solution;
+
+solution!(
+    raw,
+    part_1 = 230,
+    part_2 = 156;
+    title = "Passport Processing";
+    tags = [Parsing]
+);
 
 const CENTIMETERS_SUFFIX: &str = "cm";
 const INCHES_SUFFIX: &str = "in";
@@ -190,7 +200,7 @@ impl TryFrom<&str> for Passport {
     }
 }
 
-fn count_valid(input: String, is_valid: impl Fn(&Passport) -> bool) -> Result<String> {
+fn count_valid(input: String, is_valid: impl Fn(&Passport) -> bool) -> Result<Answer> {
     let passports: Vec<Passport> = Result::from_iter(
         input
             .split("\n\n")
@@ -203,14 +213,14 @@ fn count_valid(input: String, is_valid: impl Fn(&Passport) -> bool) -> Result<St
         .iter()
         .filter(|&passport| is_valid(passport))
         .count()
-        .to_string());
+        .into());
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     return count_valid(input, |passport| passport.has_required_fields());
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     return count_valid(input, |passport| {
         passport.has_required_fields() && passport.is_valid()
     });
@@ -1,6 +1,18 @@
 use anyhow::{bail, Result};
 use thiserror::Error;
 
+use crate::answer::Answer;
+use crate::solution;
+
+solution!(
+    raw,
+    part_1 = 994,
+    part_2 = 741;
+    title = "Binary Boarding";
+    tags = [Math];
+    notes = "seat codes are decoded by halving the row and column ranges"
+);
+
 enum Direction {
     Back,
     Right,
@@ -81,24 +93,24 @@ fn seat_id_conversion(directions: Vec<Direction>) -> Result<i32> {
     return Ok(row_start * 8 + col_start);
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let seat_ids: Vec<i32> =
         Result::from_iter(parse_input(input)?.into_iter().map(seat_id_conversion))?;
 
     match seat_ids.iter().max() {
-        Some(max_id) => return Ok(max_id.to_string()),
+        Some(max_id) => return Ok((*max_id).into()),
         None => bail!("no seats specified, can't find max"),
     }
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let mut seat_ids: Vec<i32> =
         Result::from_iter(parse_input(input)?.into_iter().map(seat_id_conversion))?;
     seat_ids.sort();
 
     for idx in 1..(seat_ids.len()) {
         if seat_ids[idx] - seat_ids[idx - 1] == 2 {
-            return Ok((seat_ids[idx] - 1).to_string());
+            return Ok((seat_ids[idx] - 1).into());
         }
     }
 
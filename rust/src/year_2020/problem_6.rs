@@ -2,6 +2,17 @@ use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 
+use crate::answer::Answer;
+use crate::solution;
+
+solution!(
+    raw,
+    part_1 = 6714,
+    part_2 = 3435;
+    title = "Custom Customs";
+    tags = [Sets]
+);
+
 type Answers = Vec<char>;
 
 fn parse_input(input: String) -> Vec<Vec<Answers>> {
@@ -17,7 +28,7 @@ fn parse_input(input: String) -> Vec<Vec<Answers>> {
         .collect();
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let groups_answers = parse_input(input);
 
     let mut anyone_answered_yes_sum: usize = 0;
@@ -33,10 +44,10 @@ pub fn part_1(input: String) -> Result<String> {
         anyone_answered_yes_sum += answered_yes.len();
     }
 
-    return Ok(anyone_answered_yes_sum.to_string());
+    return Ok(anyone_answered_yes_sum.into());
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let groups_answers = parse_input(input);
 
     let mut everyone_answered_yes_sum: usize = 0;
@@ -58,5 +69,5 @@ pub fn part_2(input: String) -> Result<String> {
             .count();
     }
 
-    return Ok(everyone_answered_yes_sum.to_string());
+    return Ok(everyone_answered_yes_sum.into());
 }
@@ -3,12 +3,24 @@ use std::collections::HashMap;
 use anyhow::Result;
 use thiserror::Error;
 
+use crate::answer::Answer;
+use crate::params::Param;
+use crate::solution;
 use crate::static_regex;
 
+solution!(
+    raw,
+    part_1 = 139,
+    part_2 = 58175;
+    title = "Handy Haversacks";
+    tags = [Graph];
+    params = [TARGET_BAG]
+);
+
 static_regex!(outer_re, r"^([a-z]+ [a-z]+) bags contain (.+)\.$");
 static_regex!(inner_re, r"(\d+) ([a-z]+ [a-z]+) bags?");
 
-const TARGET_BAG: &str = "shiny gold";
+static TARGET_BAG: Param<String> = Param::new("target_bag", "shiny gold");
 
 #[derive(Error, Debug)]
 enum InputParseError {
@@ -122,9 +134,9 @@ pub fn get_target_containers(
         .count();
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let bags_map = parse_input(input)?;
-    return Ok(get_target_containers(TARGET_BAG, bags_map).to_string());
+    return Ok(get_target_containers(&TARGET_BAG.get()?, bags_map).into());
 }
 
 fn get_target_contained(target_bag: &str, bags_map: &HashMap<String, Vec<(String, i32)>>) -> i32 {
@@ -142,7 +154,7 @@ fn get_target_contained(target_bag: &str, bags_map: &HashMap<String, Vec<(String
         .sum();
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let bags_map = parse_input(input)?;
-    return Ok(get_target_contained(TARGET_BAG, &bags_map).to_string());
+    return Ok(get_target_contained(&TARGET_BAG.get()?, &bags_map).into());
 }
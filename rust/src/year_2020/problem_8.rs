@@ -3,8 +3,19 @@ use std::collections::HashSet;
 use anyhow::{anyhow, bail, Result};
 use thiserror::Error;
 
+use crate::answer::Answer;
+use crate::solution;
 use crate::static_regex;
 
+solution!(
+    raw,
+    part_1 = 1475,
+    part_2 = 1270;
+    title = "Handheld Halting";
+    tags = [Vm];
+    notes = "part 2 only tries to flip the jmp and nop instructions executed by the looping program"
+);
+
 #[derive(Debug)]
 enum Instruction {
     NoOperation(i32),
@@ -143,12 +154,12 @@ impl TryFrom<String> for Program {
     }
 }
 
-pub fn part_1(input: String) -> Result<String> {
+pub fn part_1(input: String) -> Result<Answer> {
     let program = Program::try_from(input)?;
-    return Ok(program.execute(None)?.accumulator.to_string());
+    return Ok(program.execute(None)?.accumulator.into());
 }
 
-pub fn part_2(input: String) -> Result<String> {
+pub fn part_2(input: String) -> Result<Answer> {
     let program = Program::try_from(input)?;
-    return Ok(program.execute_with_fix()?.accumulator.to_string());
+    return Ok(program.execute_with_fix()?.accumulator.into());
 }
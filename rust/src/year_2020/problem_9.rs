@@ -2,6 +2,21 @@ use std::collections::{HashSet, VecDeque};
 
 use anyhow::{bail, ensure, Result};
 
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::Param;
+use crate::solution;
+
+solution!(
+    Problem9,
+    part_1 = 27911108,
+    part_2 = 4023754;
+    title = "Encoding Error";
+    tags = [Search];
+    variants = [part_2(window = sliding_window)];
+    params = [PREAMBLE_LEN]
+);
+
 fn parse_input(input: &str) -> Result<Vec<i64>> {
     return Result::from_iter(
         input
@@ -22,17 +37,18 @@ fn two_sum(target: i64, pool: &HashSet<i64>) -> Option<(i64, i64)> {
     return None;
 }
 
-const PREAMBLE_LEN: usize = 25;
-pub fn part_1(input: String) -> Result<String> {
-    let numbers = parse_input(&input)?;
+static PREAMBLE_LEN: Param<usize> = Param::new("preamble_len", "25");
+
+fn first_invalid(numbers: &[i64]) -> Result<i64> {
+    let preamble_len = PREAMBLE_LEN.get()?;
 
     let mut set_pool: HashSet<i64> = HashSet::new();
     let mut vec_pool: VecDeque<i64> = VecDeque::new();
 
     let mut first_invalid: Option<i64> = None;
-    for n in numbers {
+    for &n in numbers {
         let pool_size = vec_pool.len();
-        if pool_size < PREAMBLE_LEN {
+        if pool_size < preamble_len {
             set_pool.insert(n);
             vec_pool.push_back(n);
             continue;
@@ -56,41 +72,79 @@ pub fn part_1(input: String) -> Result<String> {
     }
 
     if let Some(first_invalid) = first_invalid {
-        return Ok(first_invalid.to_string());
+        return Ok(first_invalid);
     }
 
     bail!("all numbers in the sequence are valid")
 }
 
-pub fn part_2(input: String) -> Result<String> {
-    let numbers = parse_input(&input)?;
-    let first_invalid = str::parse::<i64>(part_1(input)?.as_str())?;
+/// Finds the contiguous range summing to the first invalid number in a single pass, growing the
+/// range on its right while the sum is too small and shrinking it on its left while it is too big
+fn sliding_window(numbers: &[i64]) -> Result<Answer> {
+    let first_invalid = first_invalid(numbers)?;
+
+    let mut start: usize = 0;
+    let mut sum: i64 = 0;
+    for end in 0..numbers.len() {
+        sum += numbers[end];
+        while sum > first_invalid && start < end {
+            sum -= numbers[start];
+            start += 1;
+        }
 
-    for idx in 0..numbers.len() {
-        let mut sum: i64 = 0;
-        let mut sum_addends: Vec<i64> = Vec::new();
+        if sum == first_invalid && end > start {
+            let range = &numbers[start..=end];
+            if let (Some(min_addend), Some(max_addend)) = (range.iter().min(), range.iter().max()) {
+                return Ok((min_addend + max_addend).into());
+            }
+        }
+    }
 
-        let mut sum_idx: usize = idx;
-        while sum_idx < numbers.len() && sum < first_invalid {
-            sum += numbers[sum_idx];
-            sum_addends.push(numbers[sum_idx]);
+    bail!("no valid contiguous set of numbers found")
+}
 
-            sum_idx += 1;
-        }
+pub struct Problem9;
+
+impl Day for Problem9 {
+    type Parsed = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>> {
+        return parse_input(input);
+    }
+
+    fn part_1(numbers: &Vec<i64>) -> Result<Answer> {
+        return Ok(first_invalid(numbers)?.into());
+    }
 
-        ensure!(sum == sum_addends.iter().sum());
+    fn part_2(numbers: &Vec<i64>) -> Result<Answer> {
+        let first_invalid = first_invalid(numbers)?;
 
-        if sum == first_invalid {
-            let min_addend = sum_addends.iter().min();
-            let max_addend = sum_addends.iter().max();
+        for idx in 0..numbers.len() {
+            let mut sum: i64 = 0;
+            let mut sum_addends: Vec<i64> = Vec::new();
 
-            if let (Some(min_addend), Some(max_addend)) = (min_addend, max_addend) {
-                return Ok((min_addend + max_addend).to_string());
+            let mut sum_idx: usize = idx;
+            while sum_idx < numbers.len() && sum < first_invalid {
+                sum += numbers[sum_idx];
+                sum_addends.push(numbers[sum_idx]);
+
+                sum_idx += 1;
             }
 
-            bail!("min_addend or max_addend is none after sum evaluation")
+            ensure!(sum == sum_addends.iter().sum::<i64>());
+
+            if sum == first_invalid {
+                let min_addend = sum_addends.iter().min();
+                let max_addend = sum_addends.iter().max();
+
+                if let (Some(min_addend), Some(max_addend)) = (min_addend, max_addend) {
+                    return Ok((min_addend + max_addend).into());
+                }
+
+                bail!("min_addend or max_addend is none after sum evaluation")
+            }
         }
-    }
 
-    bail!("no valid contiguous set of numbers found")
+        bail!("no valid contiguous set of numbers found")
+    }
 }